@@ -1,4 +1,4 @@
-use crate::bindings::Binding;
+use crate::bindings::{Binding, BindingShape};
 use crate::state::InputState;
 
 use super::ActionKind;
@@ -11,16 +11,22 @@ pub struct Clamped<T>(pub T);
 impl<T> ActionKind for Clamped<T>
 where
     T: ActionKind,
+    T::Value: Clamp,
     T::Output: Clamp,
 {
+    type Value = T::Value;
     type Output = T::Output;
 
-    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Output> {
+    fn accepts(&self, shape: BindingShape) -> bool {
+        self.0.accepts(shape)
+    }
+
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
         self.0.get(state, binding).map(|v| v.clamp(1.0))
     }
 
-    fn reduce(&mut self, inputs: &[Self::Output]) -> Self::Output {
-        self.0.reduce(inputs).clamp(1.0)
+    fn reduce(&mut self, state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
+        self.0.reduce(state, inputs).clamp(1.0)
     }
 }
 
@@ -0,0 +1,200 @@
+use std::f32::consts::{PI, TAU};
+use std::time::Duration;
+
+use crate::bindings::{Binding, BindingShape};
+use crate::state::InputState;
+
+use super::{ActionKind, Axis2dAction};
+
+/// Describes a flick stick camera, usually bound to a gamepad's right stick.
+///
+/// Flicking the stick in a direction turns the camera to face that direction
+/// over a short time. Rotating the stick while it's held turns the camera by the
+/// same angle. The output is how far to turn the camera during this update, in
+/// radians, with positive values turning clockwise.
+///
+/// Flicks are animated using the time given to
+/// [`Session::begin_update`](crate::Session::begin_update).
+#[derive(Debug)]
+pub struct FlickStickAction {
+    flick_time: Duration,
+    threshold: f32,
+    smoothing: f32,
+    update: Option<u64>,
+    output: f32,
+    last_angle: Option<f32>,
+    rotation: f32,
+    flick: Option<Flick>,
+}
+
+#[derive(Debug)]
+struct Flick {
+    target: f32,
+    elapsed: f32,
+    progress: f32,
+}
+
+impl FlickStickAction {
+    /// Create a flick stick action with a flick time of 100ms, a threshold of
+    /// 0.9 and no smoothing.
+    pub fn new() -> Self {
+        Self {
+            flick_time: Duration::from_millis(100),
+            threshold: 0.9,
+            smoothing: 0.0,
+            update: None,
+            output: 0.0,
+            last_angle: None,
+            rotation: 0.0,
+            flick: None,
+        }
+    }
+
+    /// Set how long it takes to turn toward a flicked direction.
+    pub fn with_flick_time(self, flick_time: Duration) -> Self {
+        Self { flick_time, ..self }
+    }
+
+    /// Set how far the stick has to be pushed to start a flick.
+    pub fn with_threshold(self, threshold: f32) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Set how much rotation is smoothed between updates, from 0.0 for none to
+    /// just under 1.0 for a lot.
+    pub fn with_smoothing(self, smoothing: f32) -> Self {
+        Self { smoothing, ..self }
+    }
+
+    fn advance(&mut self, [x, y]: [f32; 2], delta: f32) -> f32 {
+        let mut turn = 0.0;
+
+        if (x * x + y * y).sqrt() >= self.threshold {
+            let angle = x.atan2(y);
+
+            match self.last_angle {
+                None => {
+                    self.flick = Some(Flick {
+                        target: angle,
+                        elapsed: 0.0,
+                        progress: 0.0,
+                    });
+                }
+                Some(last_angle) => {
+                    let change = (angle - last_angle + PI).rem_euclid(TAU) - PI;
+                    self.rotation =
+                        self.rotation * self.smoothing + change * (1.0 - self.smoothing);
+                    turn += self.rotation;
+                }
+            }
+
+            self.last_angle = Some(angle);
+        } else {
+            self.last_angle = None;
+            self.rotation = 0.0;
+        }
+
+        if let Some(flick) = &mut self.flick {
+            flick.elapsed += delta;
+
+            let flick_time = self.flick_time.as_secs_f32();
+            let t = if flick_time > 0.0 {
+                (flick.elapsed / flick_time).min(1.0)
+            } else {
+                1.0
+            };
+
+            // Ease out so that the flick slows down as it reaches its target.
+            let progress = 1.0 - (1.0 - t).powi(2);
+            turn += flick.target * (progress - flick.progress);
+            flick.progress = progress;
+
+            if t >= 1.0 {
+                self.flick = None;
+            }
+        }
+
+        turn
+    }
+}
+
+impl Default for FlickStickAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActionKind for FlickStickAction {
+    type Value = [f32; 2];
+    type Output = f32;
+
+    fn accepts(&self, shape: BindingShape) -> bool {
+        shape == BindingShape::Axis2d
+    }
+
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
+        Axis2dAction.get(state, binding)
+    }
+
+    fn reduce(&mut self, state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
+        if self.update != Some(state.update_count()) {
+            self.update = Some(state.update_count());
+
+            let stick = Axis2dAction.reduce(state, inputs);
+            self.output = self.advance(stick, state.delta().as_secs_f32());
+        }
+
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Axis2d, Axis2dBinding, Event, GamepadAxis2d};
+
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    fn update(action: &mut FlickStickAction, state: &mut InputState, stick: [f32; 2]) -> f32 {
+        let axis = Axis2d::Gamepad(GamepadAxis2d::RightStick);
+        let binding = Binding::Axis2d(Axis2dBinding::Axis {
+            axis,
+            sensitivity: 1.0,
+        });
+
+        state.handle_event(Event::Axis2dChanged(axis, stick));
+        state.begin_update(Duration::from_millis(50));
+
+        let value = action.get(state, &binding);
+        let turn = action.reduce(state, &[value]);
+        assert_eq!(action.reduce(state, &[value]), turn);
+
+        state.end_update();
+        turn
+    }
+
+    #[test]
+    fn flick_and_rotate() {
+        let mut state = InputState::new();
+        let mut action = FlickStickAction::new();
+
+        assert_eq!(update(&mut action, &mut state, [0.0, 0.0]), 0.0);
+
+        let first = update(&mut action, &mut state, [1.0, 0.0]);
+        let second = update(&mut action, &mut state, [1.0, 0.0]);
+        assert!(first > second);
+        assert_close(first + second, PI / 2.0);
+        assert_eq!(update(&mut action, &mut state, [1.0, 0.0]), 0.0);
+
+        assert_close(update(&mut action, &mut state, [0.0, -1.0]), PI / 2.0);
+        assert_close(update(&mut action, &mut state, [1.0, 0.0]), -PI / 2.0);
+
+        assert_eq!(update(&mut action, &mut state, [0.0, 0.0]), 0.0);
+        let flick = update(&mut action, &mut state, [-1.0, 0.0])
+            + update(&mut action, &mut state, [-1.0, 0.0]);
+        assert_close(flick, -PI / 2.0);
+    }
+}
@@ -1,15 +1,47 @@
 mod clamped;
+mod flick_stick;
+mod radial;
+mod selector;
 
-use crate::bindings::{Axis1dBinding, Axis2dBinding, Axis3dBinding, Binding};
+use std::fmt::Debug;
+
+use crate::bindings::{Axis1dBinding, Axis2dBinding, Axis3dBinding, Binding, BindingShape};
 use crate::state::InputState;
 
 pub use clamped::*;
-
+pub use flick_stick::*;
+pub use radial::*;
+pub use selector::*;
+
+/// Defines how an action reads its bindings and combines them into a value.
+///
+/// Actions are created with
+/// [`ActionSet::create_action`](crate::ActionSet::create_action), which needs
+/// the kind to be `Send + Sync + 'static` and its `Output` to be `Send`, so
+/// that sessions can list their actions. `Output` also needs to implement
+/// [`Debug`] so that [`ActionInfo`](crate::ActionInfo) can show it.
 pub trait ActionKind {
-    type Output;
-
-    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Output>;
-    fn reduce(&mut self, inputs: &[Self::Output]) -> Self::Output;
+    /// The value read from a single binding.
+    type Value;
+
+    /// The value of the action, combined from all of its bindings.
+    type Output: Clone + Debug;
+
+    /// Tells whether this kind of action can read bindings of the given shape.
+    /// Used to validate bindings against a session's actions.
+    fn accepts(&self, shape: BindingShape) -> bool;
+
+    /// Read the value of a binding, or `None` if this kind of action can't use
+    /// it.
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value>;
+
+    /// Combine the values of all of an action's bindings. `inputs` holds one
+    /// entry per binding, in the same order as the bindings.
+    ///
+    /// This is called every time the action is read, which may happen more than
+    /// once per update. Actions that keep state between updates can use
+    /// [`InputState::update_count`] to tell updates apart.
+    fn reduce(&mut self, state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output;
 }
 
 /// Describes an action that happens as an instantaneous event, like a character
@@ -18,19 +50,29 @@ pub trait ActionKind {
 pub struct EventAction;
 
 impl ActionKind for EventAction {
+    type Value = bool;
     type Output = bool;
 
-    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Output> {
-        let binding = match binding {
-            Binding::Button(inner) => inner,
-            _ => return None,
-        };
+    fn accepts(&self, shape: BindingShape) -> bool {
+        matches!(
+            shape,
+            BindingShape::Button | BindingShape::Threshold | BindingShape::Chord
+        )
+    }
 
-        Some(state.is_button_just_down(*binding))
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
+        match binding {
+            Binding::Button(button) => Some(state.is_button_just_down(*button)),
+            Binding::Threshold(threshold) => Some(state.threshold_state(threshold).just_pressed),
+            Binding::Chord(chord) => Some(
+                state.is_button_down(chord.modifier) && state.is_button_just_down(chord.button),
+            ),
+            _ => None,
+        }
     }
 
-    fn reduce(&mut self, inputs: &[Self::Output]) -> Self::Output {
-        inputs.iter().any(|x| *x)
+    fn reduce(&mut self, _state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
+        inputs.iter().flatten().any(|x| *x)
     }
 }
 
@@ -40,19 +82,32 @@ impl ActionKind for EventAction {
 pub struct BoolAction;
 
 impl ActionKind for BoolAction {
+    type Value = bool;
     type Output = bool;
 
-    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Output> {
-        let binding = match binding {
-            Binding::Button(inner) => inner,
-            _ => return None,
-        };
+    fn accepts(&self, shape: BindingShape) -> bool {
+        matches!(
+            shape,
+            BindingShape::Button | BindingShape::Threshold | BindingShape::Chord
+        )
+    }
 
-        Some(state.is_button_down(*binding))
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
+        match binding {
+            Binding::Button(button) => Some(state.is_button_down(*button)),
+            Binding::Threshold(threshold) => {
+                let threshold = state.threshold_state(threshold);
+                Some(threshold.just_pressed || threshold.pressed)
+            }
+            Binding::Chord(chord) => {
+                Some(state.is_button_down(chord.modifier) && state.is_button_down(chord.button))
+            }
+            _ => None,
+        }
     }
 
-    fn reduce(&mut self, inputs: &[Self::Output]) -> Self::Output {
-        inputs.iter().any(|x| *x)
+    fn reduce(&mut self, _state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
+        inputs.iter().flatten().any(|x| *x)
     }
 }
 
@@ -62,9 +117,14 @@ impl ActionKind for BoolAction {
 pub struct Axis1dAction;
 
 impl ActionKind for Axis1dAction {
+    type Value = f32;
     type Output = f32;
 
-    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Output> {
+    fn accepts(&self, shape: BindingShape) -> bool {
+        shape == BindingShape::Axis1d
+    }
+
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
         let binding = match binding {
             Binding::Axis1d(inner) => inner,
             _ => return None,
@@ -75,11 +135,14 @@ impl ActionKind for Axis1dAction {
                 neg,
                 pos,
                 sensitivity,
+                ramp,
             } => {
-                let is_neg = -(state.is_button_down(*neg) as u8 as f32);
-                let is_pos = state.is_button_down(*pos) as u8 as f32;
+                let value = match ramp {
+                    Some(ramp) => state.ramped_axis(*neg, *pos, ramp),
+                    None => state.button_axis(*neg, *pos),
+                };
 
-                Some((is_neg + is_pos) * sensitivity)
+                Some(value * sensitivity)
             }
             Axis1dBinding::Axis { axis, sensitivity } => {
                 Some(state.get_axis1d(*axis) * sensitivity)
@@ -87,8 +150,8 @@ impl ActionKind for Axis1dAction {
         }
     }
 
-    fn reduce(&mut self, inputs: &[Self::Output]) -> Self::Output {
-        inputs.iter().sum::<f32>()
+    fn reduce(&mut self, _state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
+        inputs.iter().flatten().sum::<f32>()
     }
 }
 
@@ -98,9 +161,14 @@ impl ActionKind for Axis1dAction {
 pub struct Axis2dAction;
 
 impl ActionKind for Axis2dAction {
+    type Value = [f32; 2];
     type Output = [f32; 2];
 
-    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Output> {
+    fn accepts(&self, shape: BindingShape) -> bool {
+        shape == BindingShape::Axis2d
+    }
+
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
         let binding = match binding {
             Binding::Axis2d(inner) => inner,
             _ => return None,
@@ -121,9 +189,10 @@ impl ActionKind for Axis2dAction {
         }
     }
 
-    fn reduce(&mut self, inputs: &[Self::Output]) -> Self::Output {
+    fn reduce(&mut self, _state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
         inputs
             .iter()
+            .flatten()
             .fold([0.0, 0.0], |[ax, ay], [bx, by]| [ax + bx, ay + by])
     }
 }
@@ -134,9 +203,14 @@ impl ActionKind for Axis2dAction {
 pub struct Axis3dAction;
 
 impl ActionKind for Axis3dAction {
+    type Value = [f32; 3];
     type Output = [f32; 3];
 
-    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Output> {
+    fn accepts(&self, shape: BindingShape) -> bool {
+        shape == BindingShape::Axis3d
+    }
+
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
         let binding = match binding {
             Binding::Axis3d(inner) => inner,
             _ => return None,
@@ -153,9 +227,10 @@ impl ActionKind for Axis3dAction {
         }
     }
 
-    fn reduce(&mut self, inputs: &[Self::Output]) -> Self::Output {
+    fn reduce(&mut self, _state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
         inputs
             .iter()
+            .flatten()
             .fold([0.0, 0.0, 0.0], |[ax, ay, az], [bx, by, bz]| {
                 [ax + bx, ay + by, az + bz]
             })
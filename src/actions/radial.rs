@@ -0,0 +1,176 @@
+use std::f32::consts::TAU;
+
+use crate::bindings::{Binding, BindingShape};
+use crate::state::InputState;
+
+use super::{ActionKind, Axis2dAction};
+
+/// Describes picking a sector by pointing a 2D axis, like a weapon wheel or a
+/// radial chat menu.
+///
+/// Sector 0 is centered on the positive Y axis, and sectors count up going
+/// clockwise toward the positive X axis. The selection is sticky: it stays put
+/// when the axis returns to center, and is committed on the update where that
+/// happens.
+#[derive(Debug)]
+pub struct RadialAction {
+    sectors: usize,
+    offset: f32,
+    deadzone: f32,
+    selected: Option<usize>,
+    held: bool,
+    committed: Option<usize>,
+    update: u64,
+}
+
+/// The output of a [`RadialAction`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RadialSelection {
+    /// The sector most recently pointed at, if any.
+    pub selected: Option<usize>,
+
+    /// Whether the axis is currently outside of the deadzone.
+    pub active: bool,
+
+    /// The sector that was selected when the axis returned to center. This is
+    /// only set during the update where that happened.
+    pub committed: Option<usize>,
+}
+
+impl RadialAction {
+    /// Create a radial action with the given number of sectors, no offset, and a
+    /// deadzone of 0.5.
+    pub fn new(sectors: usize) -> Self {
+        Self {
+            sectors,
+            offset: 0.0,
+            deadzone: 0.5,
+            selected: None,
+            held: false,
+            committed: None,
+            update: 0,
+        }
+    }
+
+    /// Rotate all sectors clockwise by the given angle, in radians.
+    pub fn with_offset(self, offset: f32) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Set how far the axis has to be pushed before a sector is selected.
+    pub fn with_deadzone(self, deadzone: f32) -> Self {
+        Self { deadzone, ..self }
+    }
+
+    fn sector(&self, [x, y]: [f32; 2]) -> usize {
+        let width = TAU / self.sectors as f32;
+        let angle = (x.atan2(y) - self.offset + width / 2.0).rem_euclid(TAU);
+
+        ((angle / width) as usize).min(self.sectors - 1)
+    }
+}
+
+impl ActionKind for RadialAction {
+    type Value = [f32; 2];
+    type Output = RadialSelection;
+
+    fn accepts(&self, shape: BindingShape) -> bool {
+        shape == BindingShape::Axis2d
+    }
+
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
+        Axis2dAction.get(state, binding)
+    }
+
+    fn reduce(&mut self, state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
+        let [x, y] = Axis2dAction.reduce(state, inputs);
+
+        if state.update_count() != self.update {
+            self.update = state.update_count();
+            self.committed = None;
+        }
+
+        if self.sectors > 0 && (x * x + y * y).sqrt() > self.deadzone {
+            self.selected = Some(self.sector([x, y]));
+            self.held = true;
+        } else if self.held {
+            self.held = false;
+            self.committed = self.selected;
+        }
+
+        RadialSelection {
+            selected: self.selected,
+            active: self.held,
+            committed: self.committed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Axis2d, Axis2dBinding, Event, GamepadAxis2d};
+
+    use super::*;
+
+    fn read(action: &mut RadialAction, state: &InputState) -> RadialSelection {
+        let binding = Binding::Axis2d(Axis2dBinding::Axis {
+            axis: Axis2d::Gamepad(GamepadAxis2d::RightStick),
+            sensitivity: 1.0,
+        });
+
+        let value = action.get(state, &binding);
+        action.reduce(state, &[value])
+    }
+
+    fn point(state: &mut InputState, value: [f32; 2]) {
+        state.handle_event(Event::Axis2dChanged(
+            Axis2d::Gamepad(GamepadAxis2d::RightStick),
+            value,
+        ));
+    }
+
+    #[test]
+    fn sectors() {
+        let action = RadialAction::new(4);
+        assert_eq!(action.sector([0.0, 1.0]), 0);
+        assert_eq!(action.sector([1.0, 0.0]), 1);
+        assert_eq!(action.sector([0.0, -1.0]), 2);
+        assert_eq!(action.sector([-1.0, 0.0]), 3);
+        assert_eq!(action.sector([-0.1, 1.0]), 0);
+
+        let action = RadialAction::new(4).with_offset(TAU / 8.0);
+        assert_eq!(action.sector([-0.1, 1.0]), 3);
+        assert_eq!(action.sector([0.1, 1.0]), 0);
+    }
+
+    #[test]
+    fn commit_on_release() {
+        let mut state = InputState::new();
+        let mut action = RadialAction::new(8).with_deadzone(0.3);
+
+        point(&mut state, [0.1, 0.1]);
+        assert_eq!(read(&mut action, &state), RadialSelection::default());
+        state.end_update();
+
+        point(&mut state, [1.0, 0.0]);
+        let selection = read(&mut action, &state);
+        assert_eq!(selection.selected, Some(2));
+        assert!(selection.active);
+        assert_eq!(selection.committed, None);
+        state.end_update();
+
+        point(&mut state, [0.0, 0.0]);
+        let expected = RadialSelection {
+            selected: Some(2),
+            active: false,
+            committed: Some(2),
+        };
+        assert_eq!(read(&mut action, &state), expected);
+        assert_eq!(read(&mut action, &state), expected);
+        state.end_update();
+
+        let selection = read(&mut action, &state);
+        assert_eq!(selection.selected, Some(2));
+        assert_eq!(selection.committed, None);
+    }
+}
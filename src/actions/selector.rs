@@ -0,0 +1,133 @@
+use std::fmt::Debug;
+
+use crate::bindings::{Binding, BindingShape};
+use crate::state::InputState;
+
+use super::{ActionKind, EventAction};
+
+/// Describes picking one of several options, like switching weapons with the
+/// number keys. The output is the index of the binding that was just pressed.
+///
+/// If several bindings are pressed during the same update, the one listed first
+/// wins.
+#[derive(Debug, Default)]
+pub struct SelectorAction;
+
+impl SelectorAction {
+    /// Map each binding index to a value, producing the value for whichever
+    /// binding was pressed instead of its index.
+    pub fn with_options<T: Clone + Debug>(self, options: Vec<T>) -> MappedSelectorAction<T> {
+        MappedSelectorAction { options }
+    }
+}
+
+impl ActionKind for SelectorAction {
+    type Value = bool;
+    type Output = Option<usize>;
+
+    fn accepts(&self, shape: BindingShape) -> bool {
+        matches!(
+            shape,
+            BindingShape::Button | BindingShape::Threshold | BindingShape::Chord
+        )
+    }
+
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
+        EventAction.get(state, binding)
+    }
+
+    fn reduce(&mut self, _state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
+        inputs.iter().position(|input| *input == Some(true))
+    }
+}
+
+/// A [`SelectorAction`] that produces a value from a list instead of a binding
+/// index.
+///
+/// Created with [`SelectorAction::with_options`].
+#[derive(Debug)]
+pub struct MappedSelectorAction<T> {
+    options: Vec<T>,
+}
+
+impl<T: Clone + Debug> ActionKind for MappedSelectorAction<T> {
+    type Value = bool;
+    type Output = Option<T>;
+
+    fn accepts(&self, shape: BindingShape) -> bool {
+        matches!(
+            shape,
+            BindingShape::Button | BindingShape::Threshold | BindingShape::Chord
+        )
+    }
+
+    fn get(&mut self, state: &InputState, binding: &Binding) -> Option<Self::Value> {
+        SelectorAction.get(state, binding)
+    }
+
+    fn reduce(&mut self, state: &InputState, inputs: &[Option<Self::Value>]) -> Self::Output {
+        let index = SelectorAction.reduce(state, inputs)?;
+        self.options.get(index).cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Axis1d, Axis1dBinding, Event, GamepadAxis1d, KeyboardKey};
+
+    use super::*;
+
+    fn select<K: ActionKind>(
+        action: &mut K,
+        state: &InputState,
+        bindings: &[Binding],
+    ) -> K::Output {
+        let inputs: Vec<_> = bindings
+            .iter()
+            .map(|binding| action.get(state, binding))
+            .collect();
+
+        action.reduce(state, &inputs)
+    }
+
+    #[test]
+    fn selector_index() {
+        let mut state = InputState::new();
+        let bindings = [
+            KeyboardKey::One.into(),
+            Binding::Axis1d(Axis1dBinding::Axis {
+                axis: Axis1d::Gamepad(GamepadAxis1d::LeftStickX),
+                sensitivity: 1.0,
+            }),
+            KeyboardKey::Three.into(),
+        ];
+        let mut action = SelectorAction;
+
+        assert_eq!(select(&mut action, &state, &bindings), None);
+
+        state.handle_event(Event::ButtonPressed(KeyboardKey::Three.into()));
+        assert_eq!(select(&mut action, &state, &bindings), Some(2));
+
+        state.end_update();
+        assert_eq!(select(&mut action, &state, &bindings), None);
+    }
+
+    #[test]
+    fn selector_options() {
+        #[derive(Debug, Clone, PartialEq)]
+        enum Weapon {
+            Pistol,
+            Shotgun,
+        }
+
+        let mut state = InputState::new();
+        let bindings = [KeyboardKey::One.into(), KeyboardKey::Two.into()];
+        let mut action = SelectorAction.with_options(vec![Weapon::Pistol, Weapon::Shotgun]);
+
+        state.handle_event(Event::ButtonPressed(KeyboardKey::Two.into()));
+        assert_eq!(
+            select(&mut action, &state, &bindings),
+            Some(Weapon::Shotgun)
+        );
+    }
+}
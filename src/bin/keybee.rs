@@ -0,0 +1,475 @@
+//! Command-line tool for working with Keybee bindings files.
+//!
+//! Files can be JSON, TOML or RON, chosen by their extension. Each file can be
+//! either a plain set of bindings or a versioned bindings file with a
+//! `bindings` key.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::Context;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+
+use keybee::{Axis1d, Axis2d, Binding, Bindings, BindingsFile, Button, ConflictRules, Input};
+
+const USAGE: &str = "\
+Usage: keybee <command> [args]
+
+Commands:
+    validate <file>...        Check that every binding in the files parses
+                              and isn't repeated, and warn about inputs
+                              shared between actions. Whether each binding
+                              fits its action depends on the game, so use
+                              Session::validate_bindings for that
+    fmt [--check] <file>      Print a file in its canonical layout, or with
+                              --check, fail if it isn't already formatted
+    fmt --write <file>        Rewrite a file in its canonical layout
+    convert <input> <output>  Convert a file between JSON, TOML and RON
+    diff <old> <new>          List the actions whose bindings changed,
+                              ignoring bindings that only moved
+    inputs                    List every input name
+    which <file> <input>      List the actions that an input triggers";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run a command, returning whether it succeeded.
+fn run(args: &[&str]) -> anyhow::Result<bool> {
+    match args {
+        ["validate", files @ ..] if !files.is_empty() => validate(files),
+        ["fmt", file] => fmt(file, FmtMode::Print),
+        ["fmt", "--check", file] => fmt(file, FmtMode::Check),
+        ["fmt", "--write", file] => fmt(file, FmtMode::Write),
+        ["convert", input, output] => convert(input, output),
+        ["diff", old, new] => diff(old, new),
+        ["inputs"] => inputs(),
+        ["which", file, input] => which(file, input),
+        ["help" | "--help" | "-h"] => {
+            println!("{}", USAGE);
+            Ok(true)
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            Ok(false)
+        }
+    }
+}
+
+fn validate(files: &[&str]) -> anyhow::Result<bool> {
+    let mut ok = true;
+
+    for file in files {
+        let bindings = match load_bindings(file) {
+            Ok(bindings) => bindings,
+            Err(err) => {
+                eprintln!("error: {:#}", err);
+                ok = false;
+                continue;
+            }
+        };
+
+        let mut actions = 0;
+        let mut duplicates = false;
+
+        for (set_name, set) in &bindings.action_sets {
+            for (action_name, list) in &set.actions {
+                actions += 1;
+
+                for (index, binding) in list.iter().enumerate() {
+                    if list[..index].contains(binding) {
+                        eprintln!(
+                            "error: in {}, binding {} of '{}/{}' is a duplicate: {}",
+                            file,
+                            index,
+                            set_name,
+                            action_name,
+                            describe(binding)
+                        );
+                        duplicates = true;
+                    }
+                }
+            }
+        }
+
+        // Without the game's rules, any two action sets might be active at
+        // once, so shared inputs are only worth a warning.
+        for conflict in bindings.conflicts(&ConflictRules::new()) {
+            eprintln!(
+                "warning: in {}, '{}' and '{}' both use {}",
+                file, conflict.action, conflict.other, conflict.input
+            );
+        }
+
+        if duplicates {
+            ok = false;
+        } else {
+            println!("ok: {} ({} actions)", file, actions);
+        }
+    }
+
+    Ok(ok)
+}
+
+enum FmtMode {
+    Print,
+    Check,
+    Write,
+}
+
+fn fmt(file: &str, mode: FmtMode) -> anyhow::Result<bool> {
+    let path = Path::new(file);
+    let format = Format::from_path(path)?;
+    let text = read(path)?;
+    let formatted = Document::parse(&text, format)
+        .with_context(|| format!("failed to parse {}", file))?
+        .write(format)?;
+
+    match mode {
+        FmtMode::Print => {
+            print!("{}", formatted);
+            Ok(true)
+        }
+        FmtMode::Check => {
+            if text == formatted {
+                Ok(true)
+            } else {
+                eprintln!("{} is not formatted", file);
+                Ok(false)
+            }
+        }
+        FmtMode::Write => {
+            if text != formatted {
+                fs::write(path, formatted).with_context(|| format!("failed to write {}", file))?;
+            }
+
+            Ok(true)
+        }
+    }
+}
+
+fn convert(input: &str, output: &str) -> anyhow::Result<bool> {
+    let input_path = Path::new(input);
+    let output_path = Path::new(output);
+
+    let text = read(input_path)?;
+    let document = Document::parse(&text, Format::from_path(input_path)?)
+        .with_context(|| format!("failed to parse {}", input))?;
+    let converted = document.write(Format::from_path(output_path)?)?;
+
+    fs::write(output_path, converted).with_context(|| format!("failed to write {}", output))?;
+    Ok(true)
+}
+
+fn diff(old: &str, new: &str) -> anyhow::Result<bool> {
+    let old = load_bindings(old)?;
+    let new = load_bindings(new)?;
+
+    // Only list actions that gained or lost bindings, not ones whose bindings
+    // just moved around.
+    let mut changes = old.diff(&new);
+    changes.retain(|change| !change.is_reordered());
+
+    for change in &changes {
+        println!("{}", change.action);
+
+        match (&change.old, &change.new) {
+            (None, _) => println!("  (added)"),
+            (_, None) => println!("  (removed)"),
+            _ => {}
+        }
+
+        for binding in change.removed() {
+            println!("  - {}", describe(&binding));
+        }
+        for binding in change.added() {
+            println!("  + {}", describe(&binding));
+        }
+    }
+
+    Ok(changes.is_empty())
+}
+
+fn inputs() -> anyhow::Result<bool> {
+    for button in Button::all() {
+        println!("{}\tbutton", button);
+    }
+    for axis in Axis1d::all() {
+        println!("{}\taxis1d", axis);
+    }
+    for axis in Axis2d::all() {
+        println!("{}\taxis2d", axis);
+    }
+
+    Ok(true)
+}
+
+fn which(file: &str, input: &str) -> anyhow::Result<bool> {
+    input
+        .parse::<Input>()
+        .with_context(|| format!("unknown input '{}'", input))?;
+
+    let bindings = load_bindings(file)?;
+    let mut found = false;
+
+    for (set_name, set) in &bindings.action_sets {
+        for (action_name, list) in &set.actions {
+            for binding in list {
+                let uses_input = binding
+                    .inputs()
+                    .iter()
+                    .any(|used| used.to_string() == input);
+
+                if uses_input {
+                    println!("{}/{}\t{}", set_name, action_name, describe(binding));
+                    found = true;
+                }
+            }
+        }
+    }
+
+    if !found {
+        eprintln!("{} isn't bound to any action", input);
+    }
+
+    Ok(found)
+}
+
+/// Load a file along with every file it extends, and parse its bindings.
+fn load_bindings(file: &str) -> anyhow::Result<Bindings> {
+    let path = Path::new(file);
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("invalid file name {}", file))?;
+
+    let loader = |name: &str| -> anyhow::Result<BindingsFile> {
+        let path: PathBuf = dir.join(name);
+        let text = read(&path)?;
+        Ok(Document::parse(&text, Format::from_path(&path)?)?.into_file())
+    };
+
+    BindingsFile::load(name, &loader)?.into_bindings()
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+fn describe(binding: &Binding) -> String {
+    serde_json::to_string(binding).unwrap_or_else(|_| format!("{:?}", binding))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Toml,
+    Ron,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> anyhow::Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(Self::Json),
+            Some("toml") => Ok(Self::Toml),
+            Some("ron") => Ok(Self::Ron),
+            _ => anyhow::bail!(
+                "can't tell the format of {}; expected a .json, .toml or .ron file",
+                path.display()
+            ),
+        }
+    }
+
+    fn parse<T: DeserializeOwned>(self, text: &str) -> anyhow::Result<T> {
+        Ok(match self {
+            Self::Json => serde_json::from_str(text)?,
+            Self::Toml => toml::from_str(text)?,
+            Self::Ron => ron::from_str(text)?,
+        })
+    }
+
+    fn write<T: Serialize>(self, value: &T) -> anyhow::Result<String> {
+        Ok(match self {
+            Self::Json => serde_json::to_string_pretty(value)? + "\n",
+            Self::Toml => toml::to_string(value)?,
+            Self::Ron => {
+                let config = ron::ser::PrettyConfig::default()
+                    .extensions(ron::extensions::Extensions::IMPLICIT_SOME);
+
+                ron::ser::to_string_pretty(value, config)? + "\n"
+            }
+        })
+    }
+}
+
+/// Either kind of bindings file.
+#[derive(Debug)]
+enum Document {
+    Plain(Bindings),
+    File(BindingsFile),
+}
+
+/// Used to tell versioned bindings files apart from plain bindings, which
+/// usually don't have a `bindings` key.
+#[derive(Deserialize)]
+struct Probe {
+    #[allow(dead_code)]
+    bindings: IgnoredAny,
+}
+
+impl Document {
+    fn parse(text: &str, format: Format) -> anyhow::Result<Self> {
+        let is_file = format.parse::<Probe>(text).is_ok();
+
+        if !is_file {
+            return Ok(Self::Plain(format.parse(text)?));
+        }
+
+        match format.parse(text) {
+            Ok(file) => Ok(Self::File(file)),
+            // Plain bindings can have an action set named `bindings` too.
+            Err(err) => format.parse(text).map(Self::Plain).map_err(|_| err),
+        }
+    }
+
+    fn write(&self, format: Format) -> anyhow::Result<String> {
+        match (self, format) {
+            (Self::Plain(bindings), Format::Json) => Ok(bindings.to_canonical_json()),
+            (Self::File(file), Format::Json) => Ok(file.to_canonical_json()),
+            (Self::Plain(bindings), _) => format.write(bindings),
+            (Self::File(file), _) => format.write(file),
+        }
+    }
+
+    fn into_file(self) -> BindingsFile {
+        match self {
+            Self::Plain(bindings) => BindingsFile::new(0, &bindings),
+            Self::File(file) => file,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Write `text` to a file in the temp directory, named so that tests
+    /// running at the same time don't clash.
+    fn temp_file(name: &str, text: &str) -> String {
+        let path = std::env::temp_dir().join(format!("keybee-cli-{}-{}", std::process::id(), name));
+        fs::write(&path, text).unwrap();
+        path.to_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(
+            Format::from_path(Path::new("a.json")).unwrap(),
+            Format::Json
+        );
+        assert_eq!(
+            Format::from_path(Path::new("a/b.toml")).unwrap(),
+            Format::Toml
+        );
+        assert_eq!(Format::from_path(Path::new("a.ron")).unwrap(), Format::Ron);
+        assert!(Format::from_path(Path::new("a.yaml")).is_err());
+        assert!(Format::from_path(Path::new("json")).is_err());
+    }
+
+    #[test]
+    fn documents() {
+        let plain = r#"{ "gameplay": { "jump": ["keyboard/space"] } }"#;
+        let Document::Plain(_) = Document::parse(plain, Format::Json).unwrap() else {
+            panic!("should be plain bindings");
+        };
+
+        let file =
+            r#"{ "version": 1, "bindings": { "gameplay": { "jump": ["keyboard/space"] } } }"#;
+        let Document::File(file) = Document::parse(file, Format::Json).unwrap() else {
+            panic!("should be a bindings file");
+        };
+        assert_eq!(file.version, 1);
+
+        let named_bindings = r#"{ "bindings": { "jump": ["keyboard/space"] } }"#;
+        let Document::Plain(bindings) = Document::parse(named_bindings, Format::Json).unwrap()
+        else {
+            panic!("should be plain bindings with a set named 'bindings'");
+        };
+        assert!(bindings.get("bindings").unwrap().get("jump").is_some());
+
+        let err = Document::parse(r#"{ "bindings": 5 }"#, Format::Json).unwrap_err();
+        assert!(err.to_string().contains("expected a map"), "{}", err);
+    }
+
+    #[test]
+    fn fmt_modes() {
+        let messy = temp_file("fmt.json", r#"{"gameplay":{"jump":["keyboard/space"]}}"#);
+
+        assert!(!fmt(&messy, FmtMode::Check).unwrap());
+        assert!(fmt(&messy, FmtMode::Write).unwrap());
+        assert!(fmt(&messy, FmtMode::Check).unwrap());
+
+        let named = temp_file(
+            "fmt-named.json",
+            r#"{"bindings":{"jump":["keyboard/space"]}}"#,
+        );
+        assert!(fmt(&named, FmtMode::Write).unwrap());
+        assert!(validate(&[&named]).unwrap());
+
+        fs::remove_file(messy).unwrap();
+        fs::remove_file(named).unwrap();
+    }
+
+    #[test]
+    fn diff_ignores_order() {
+        let old = temp_file(
+            "diff-old.json",
+            r#"{"g":{"a":["keyboard/space","gamepad/a"]}}"#,
+        );
+        let moved = temp_file(
+            "diff-moved.json",
+            r#"{"g":{"a":["gamepad/a","keyboard/space"]}}"#,
+        );
+        let repeated = temp_file(
+            "diff-repeated.json",
+            r#"{"g":{"a":["keyboard/space","gamepad/a","gamepad/a"]}}"#,
+        );
+
+        assert!(diff(&old, &moved).unwrap());
+        assert!(!diff(&old, &repeated).unwrap());
+        assert!(!diff(&repeated, &old).unwrap());
+
+        for file in [old, moved, repeated] {
+            fs::remove_file(file).unwrap();
+        }
+    }
+
+    #[test]
+    fn which_inputs() {
+        let file = temp_file(
+            "which.json",
+            r#"{"g":{"jump":["keyboard/space"],"move":[{"x":["keyboard/a","keyboard/d"],"y":"gamepad/leftsticky"}]}}"#,
+        );
+
+        assert!(which(&file, "keyboard/space").unwrap());
+        assert!(which(&file, "keyboard/d").unwrap());
+        assert!(which(&file, "gamepad/leftsticky").unwrap());
+        assert!(!which(&file, "keyboard/e").unwrap());
+        assert!(which(&file, "keyboard/nonsense").is_err());
+
+        fs::remove_file(file).unwrap();
+    }
+}
@@ -1,15 +1,119 @@
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Deserialize, Serialize};
 
-use crate::{Axis1d, Axis2d, Button, GamepadButton, KeyboardKey, MouseButton};
+use crate::{Axis1d, Axis2d, Button, DeviceClass, GamepadButton, Input, KeyboardKey, MouseButton};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Binding {
     Button(Button),
+    Threshold(ThresholdBinding),
+    Chord(ChordBinding),
     Axis1d(Axis1dBinding),
     Axis2d(Axis2dBinding),
     Axis3d(Axis3dBinding),
 }
 
+/// Describes which variant of [`Binding`] a binding is, without its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BindingShape {
+    Button,
+    Threshold,
+    Chord,
+    Axis1d,
+    Axis2d,
+    Axis3d,
+}
+
+impl BindingShape {
+    /// Every shape that a binding can have.
+    pub const ALL: [BindingShape; 6] = [
+        BindingShape::Button,
+        BindingShape::Threshold,
+        BindingShape::Chord,
+        BindingShape::Axis1d,
+        BindingShape::Axis2d,
+        BindingShape::Axis3d,
+    ];
+}
+
+impl Binding {
+    /// Returns the shape of this binding.
+    pub fn shape(&self) -> BindingShape {
+        match self {
+            Binding::Button(_) => BindingShape::Button,
+            Binding::Threshold(_) => BindingShape::Threshold,
+            Binding::Chord(_) => BindingShape::Chord,
+            Binding::Axis1d(_) => BindingShape::Axis1d,
+            Binding::Axis2d(_) => BindingShape::Axis2d,
+            Binding::Axis3d(_) => BindingShape::Axis3d,
+        }
+    }
+
+    /// Returns every input that this binding reads from.
+    ///
+    /// Gamepad sticks are listed along with the X and Y axes that make them up.
+    pub fn inputs(&self) -> Vec<Input> {
+        let mut inputs = Vec::new();
+        self.for_each_input(|input| inputs.push(input));
+        inputs
+    }
+
+    /// Call `f` with every input that this binding reads from, in the same
+    /// order as [`Binding::inputs`], without allocating.
+    pub fn for_each_input<F: FnMut(Input)>(&self, mut f: F) {
+        match self {
+            Binding::Button(button) => f((*button).into()),
+            Binding::Threshold(threshold) => match threshold.source {
+                ThresholdSource::Axis1d(axis, _) => f(axis.into()),
+                ThresholdSource::Axis2d(axis, _) => axis2d_inputs(axis, &mut f),
+            },
+            Binding::Chord(chord) => {
+                f(chord.modifier.into());
+                f(chord.button.into());
+            }
+            Binding::Axis1d(axis) => axis1d_inputs(axis, &mut f),
+            Binding::Axis2d(Axis2dBinding::Individual { x, y }) => {
+                axis1d_inputs(x, &mut f);
+                axis1d_inputs(y, &mut f);
+            }
+            Binding::Axis2d(Axis2dBinding::Axis { axis, .. }) => axis2d_inputs(*axis, &mut f),
+            Binding::Axis3d(Axis3dBinding::Individual { x, y, z }) => {
+                axis1d_inputs(x, &mut f);
+                axis1d_inputs(y, &mut f);
+                axis1d_inputs(z, &mut f);
+            }
+        }
+    }
+
+    /// Returns the kind of device that this binding reads from, or `None` if it
+    /// mixes inputs from different kinds of devices.
+    pub fn device_class(&self) -> Option<DeviceClass> {
+        let mut classes = self.inputs().into_iter().map(Input::device_class);
+        let first = classes.next()?;
+
+        classes.all(|class| class == first).then_some(first)
+    }
+}
+
+fn axis1d_inputs<F: FnMut(Input)>(axis: &Axis1dBinding, f: &mut F) {
+    match axis {
+        Axis1dBinding::Buttons { neg, pos, .. } => {
+            f((*neg).into());
+            f((*pos).into());
+        }
+        Axis1dBinding::Axis { axis, .. } => f((*axis).into()),
+    }
+}
+
+fn axis2d_inputs<F: FnMut(Input)>(axis: Axis2d, f: &mut F) {
+    f(axis.into());
+
+    if let Axis2d::Gamepad(stick) = axis {
+        let (x, y) = stick.components();
+        f(Axis1d::Gamepad(x).into());
+        f(Axis1d::Gamepad(y).into());
+    }
+}
+
 impl From<Button> for Binding {
     fn from(value: Button) -> Self {
         Self::Button(value)
@@ -34,6 +138,18 @@ impl From<GamepadButton> for Binding {
     }
 }
 
+impl From<ThresholdBinding> for Binding {
+    fn from(value: ThresholdBinding) -> Self {
+        Self::Threshold(value)
+    }
+}
+
+impl From<ChordBinding> for Binding {
+    fn from(value: ChordBinding) -> Self {
+        Self::Chord(value)
+    }
+}
+
 impl From<Axis1dBinding> for Binding {
     fn from(value: Axis1dBinding) -> Self {
         Self::Axis1d(value)
@@ -52,30 +168,17 @@ impl From<Axis3dBinding> for Binding {
     }
 }
 
-impl Serialize for Binding {
-    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        todo!()
-    }
-}
-
-impl<'de> Deserialize<'de> for Binding {
-    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        todo!()
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
+/// A 1D axis, read either from an analog axis or from a pair of buttons.
+///
+/// Building bindings with [`Axis1dBinding::buttons`] and
+/// [`Axis1dBinding::axis`] avoids breaking when fields are added.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Axis1dBinding {
     Buttons {
         neg: Button,
         pos: Button,
         sensitivity: f32,
+        ramp: Option<Ramp>,
     },
     Axis {
         axis: Axis1d,
@@ -83,13 +186,84 @@ pub enum Axis1dBinding {
     },
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Axis1dBinding {
+    /// An axis that reads -1 while `neg` is held and 1 while `pos` is held.
+    pub fn buttons<N: Into<Button>, P: Into<Button>>(neg: N, pos: P) -> Self {
+        Self::Buttons {
+            neg: neg.into(),
+            pos: pos.into(),
+            sensitivity: 1.0,
+            ramp: None,
+        }
+    }
+
+    /// An axis read straight from an analog input.
+    pub fn axis<A: Into<Axis1d>>(axis: A) -> Self {
+        Self::Axis {
+            axis: axis.into(),
+            sensitivity: 1.0,
+        }
+    }
+
+    /// Scale the axis by `sensitivity`.
+    pub fn with_sensitivity(self, sensitivity: f32) -> Self {
+        match self {
+            Self::Buttons { neg, pos, ramp, .. } => Self::Buttons {
+                neg,
+                pos,
+                sensitivity,
+                ramp,
+            },
+            Self::Axis { axis, .. } => Self::Axis { axis, sensitivity },
+        }
+    }
+
+    /// Smooth out an axis made of buttons. Analog axes are left unchanged,
+    /// since they can't be ramped.
+    pub fn with_ramp(self, ramp: Ramp) -> Self {
+        match self {
+            Self::Buttons {
+                neg,
+                pos,
+                sensitivity,
+                ..
+            } => Self::Buttons {
+                neg,
+                pos,
+                sensitivity,
+                ramp: Some(ramp),
+            },
+            axis @ Self::Axis { .. } => axis,
+        }
+    }
+}
+
+/// Smooths out an [`Axis1dBinding::Buttons`] axis so that it moves toward its
+/// target over time instead of jumping straight to it.
+///
+/// Rates are measured in units per second. Ramped axes only move when the
+/// session is advanced with [`Session::begin_update`](crate::Session::begin_update).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Ramp {
+    /// How fast the axis moves toward a held direction.
+    pub rise: f32,
+
+    /// How fast the axis returns to center once no buttons are held.
+    pub fall: f32,
+
+    /// Whether the axis should jump to center before moving toward the
+    /// opposite direction.
+    #[serde(default)]
+    pub snap: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Axis2dBinding {
     Individual { x: Axis1dBinding, y: Axis1dBinding },
     Axis { axis: Axis2d, sensitivity: f32 },
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Axis3dBinding {
     Individual {
         x: Axis1dBinding,
@@ -97,3 +271,45 @@ pub enum Axis3dBinding {
         z: Axis1dBinding,
     },
 }
+
+/// Turns an analog axis into a button. The button is pressed once the axis
+/// reaches `press` and is released once it falls below `release`.
+///
+/// `release` must be at most `press`. Keeping it lower stops the button from
+/// flickering when the axis rests near the threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdBinding {
+    pub source: ThresholdSource,
+    pub press: f32,
+    pub release: f32,
+}
+
+/// A button that only counts while a modifier button is held, like Ctrl+S.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChordBinding {
+    pub modifier: Button,
+    pub button: Button,
+}
+
+/// The analog value that a [`ThresholdBinding`] reads from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThresholdSource {
+    Axis1d(Axis1d, AxisDirection),
+    Axis2d(Axis2d, Axis2dDirection),
+}
+
+/// Which way along a 1D axis a [`ThresholdBinding`] measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AxisDirection {
+    Positive,
+    Negative,
+}
+
+/// Which way along a 2D axis a [`ThresholdBinding`] measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis2dDirection {
+    PositiveX,
+    NegativeX,
+    PositiveY,
+    NegativeY,
+}
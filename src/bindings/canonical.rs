@@ -0,0 +1,232 @@
+//! The canonical JSON layout for bindings, used for files that are checked
+//! into source control or diffed by players.
+//!
+//! Keys are sorted and every action is written on its own line, so changing
+//! one action's bindings changes one line of the file:
+//!
+//! ```json
+//! {
+//!   "gameplay": {
+//!     "jump": ["keyboard/space", "gamepad/a"],
+//!     "move": ["gamepad/leftstick"]
+//!   }
+//! }
+//! ```
+
+use serde::Serialize;
+
+use super::migration::{raw_bindings, RawBindings};
+use super::{Bindings, BindingsFile};
+
+const INDENT: &str = "  ";
+
+impl Bindings {
+    /// Serialize these bindings as JSON in the canonical layout. The same
+    /// bindings always produce exactly the same text.
+    pub fn to_canonical_json(&self) -> String {
+        let mut out = String::new();
+        write_raw(&mut out, &raw_bindings(self), 0);
+        out.push('\n');
+        out
+    }
+}
+
+impl BindingsFile {
+    /// Serialize this file as JSON in the canonical layout. The same file
+    /// always produces exactly the same text.
+    pub fn to_canonical_json(&self) -> String {
+        let mut out = String::new();
+        let mut fields = vec![("version", compact(&self.version))];
+
+        if !self.extends.is_empty() {
+            fields.push(("extends", list(self.extends.iter())));
+        }
+
+        let mut bindings = String::new();
+        write_raw(&mut bindings, &self.bindings, 1);
+        fields.push(("bindings", bindings));
+
+        if !self.when.is_empty() {
+            let mut when = String::from("[\n");
+
+            for (i, block) in self.when.iter().enumerate() {
+                let mut block_fields = Vec::new();
+
+                if let Some(os) = &block.os {
+                    block_fields.push(("os", compact(os)));
+                }
+                if let Some(platform) = &block.platform {
+                    block_fields.push(("platform", compact(platform)));
+                }
+                if let Some(controller) = &block.controller {
+                    block_fields.push(("controller", compact(controller)));
+                }
+
+                let mut bindings = String::new();
+                write_raw(&mut bindings, &block.bindings, 3);
+                block_fields.push(("bindings", bindings));
+
+                indent(&mut when, 2);
+                write_object(&mut when, block_fields, 2);
+
+                if i + 1 < self.when.len() {
+                    when.push(',');
+                }
+                when.push('\n');
+            }
+
+            indent(&mut when, 1);
+            when.push(']');
+            fields.push(("when", when));
+        }
+
+        write_object(&mut out, fields, 0);
+        out.push('\n');
+        out
+    }
+}
+
+/// Write action sets as an object, with each action's bindings on one line.
+fn write_raw(out: &mut String, raw: &RawBindings, depth: usize) {
+    let sets = raw
+        .iter()
+        .map(|(set_name, actions)| {
+            let mut set = String::new();
+            let actions = actions
+                .iter()
+                .map(|(action_name, bindings)| (action_name.as_str(), list(bindings.iter())))
+                .collect();
+
+            write_object(&mut set, actions, depth + 1);
+            (set_name.as_str(), set)
+        })
+        .collect();
+
+    write_object(out, sets, depth);
+}
+
+/// Write an object with one field per line, where each value has already
+/// been written.
+fn write_object(out: &mut String, fields: Vec<(&str, String)>, depth: usize) {
+    if fields.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+
+    out.push_str("{\n");
+
+    let len = fields.len();
+    for (i, (key, value)) in fields.into_iter().enumerate() {
+        indent(out, depth + 1);
+        out.push_str(&compact(key));
+        out.push_str(": ");
+        out.push_str(&value);
+
+        if i + 1 < len {
+            out.push(',');
+        }
+        out.push('\n');
+    }
+
+    indent(out, depth);
+    out.push('}');
+}
+
+/// Write a list on a single line.
+fn list<'a, T, I>(items: I) -> String
+where
+    T: Serialize + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    let items: Vec<_> = items.map(compact).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn compact<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string(value).expect("bindings can always be written as JSON")
+}
+
+fn indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ActionSetBindings, GamepadButton, KeyboardKey};
+
+    use super::*;
+
+    #[test]
+    fn canonical_bindings() {
+        let mut bindings = Bindings::new();
+        bindings.insert("ui", ActionSetBindings::new());
+
+        let mut gameplay = ActionSetBindings::new();
+        gameplay.insert("sprint", vec![KeyboardKey::LShift.into()]);
+        gameplay.insert(
+            "jump",
+            vec![KeyboardKey::Space.into(), GamepadButton::A.into()],
+        );
+        bindings.insert("gameplay", gameplay);
+
+        let json = bindings.to_canonical_json();
+        assert_eq!(
+            json,
+            concat!(
+                "{\n",
+                "  \"gameplay\": {\n",
+                "    \"jump\": [\"keyboard/space\", \"gamepad/a\"],\n",
+                "    \"sprint\": [\"keyboard/leftshift\"]\n",
+                "  },\n",
+                "  \"ui\": {}\n",
+                "}\n",
+            )
+        );
+
+        let parsed: Bindings = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.to_canonical_json(), json);
+    }
+
+    #[test]
+    fn canonical_file() {
+        let json = r#"{
+            "when": [{ "controller": "nintendo", "os": "linux", "bindings": { "gameplay": { "jump": ["gamepad/b"] } } }],
+            "bindings": { "gameplay": { "jump": ["gamepad/a"] } },
+            "extends": ["base"],
+            "version": 3
+        }"#;
+
+        let file: BindingsFile = serde_json::from_str(json).unwrap();
+        let canonical = file.to_canonical_json();
+        assert_eq!(
+            canonical,
+            concat!(
+                "{\n",
+                "  \"version\": 3,\n",
+                "  \"extends\": [\"base\"],\n",
+                "  \"bindings\": {\n",
+                "    \"gameplay\": {\n",
+                "      \"jump\": [\"gamepad/a\"]\n",
+                "    }\n",
+                "  },\n",
+                "  \"when\": [\n",
+                "    {\n",
+                "      \"os\": \"linux\",\n",
+                "      \"controller\": \"nintendo\",\n",
+                "      \"bindings\": {\n",
+                "        \"gameplay\": {\n",
+                "          \"jump\": [\"gamepad/b\"]\n",
+                "        }\n",
+                "      }\n",
+                "    }\n",
+                "  ]\n",
+                "}\n",
+            )
+        );
+
+        let reparsed: BindingsFile = serde_json::from_str(&canonical).unwrap();
+        assert_eq!(reparsed.to_canonical_json(), canonical);
+    }
+}
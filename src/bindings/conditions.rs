@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ControllerFamily;
+
+use super::migration::RawBindings;
+
+/// Describes the machine that bindings are being loaded on, used to pick which
+/// conditional sections of a [`BindingsFile`](super::BindingsFile) apply.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BindingsContext {
+    /// The target operating system, using the same names as
+    /// [`std::env::consts::OS`], like `macos` or `windows`.
+    pub os: String,
+
+    /// Platform tags supplied by the game, like `steamdeck` or `console`.
+    pub platforms: Vec<String>,
+
+    /// The families of the controllers that are connected.
+    pub controllers: Vec<ControllerFamily>,
+}
+
+impl BindingsContext {
+    /// Create a context for the operating system this program was built for,
+    /// with no platform tags or controllers.
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a platform tag.
+    pub fn with_platform<S: Into<String>>(mut self, platform: S) -> Self {
+        self.platforms.push(platform.into());
+        self
+    }
+
+    /// Add a connected controller family.
+    pub fn with_controller(mut self, family: ControllerFamily) -> Self {
+        self.controllers.push(family);
+        self
+    }
+}
+
+/// A section of a bindings file that only applies when every condition it
+/// lists matches the [`BindingsContext`].
+///
+/// ```yaml
+/// when:
+///   - os: macos
+///     bindings:
+///       editor:
+///         save: [{ modifier: "keyboard/leftsuper", button: "keyboard/s" }]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct ConditionalBlock {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller: Option<ControllerFamily>,
+    pub bindings: RawBindings,
+}
+
+impl ConditionalBlock {
+    pub fn matches(&self, context: &BindingsContext) -> bool {
+        let os = self.os.as_ref().is_none_or(|os| *os == context.os);
+        let platform = self
+            .platform
+            .as_ref()
+            .is_none_or(|platform| context.platforms.contains(platform));
+        let controller = self
+            .controller
+            .is_none_or(|family| context.controllers.contains(&family));
+
+        os && platform && controller
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Binding, BindingsFile, ChordBinding, GamepadButton, KeyboardKey, MigrationChange,
+        Migrations,
+    };
+
+    use super::*;
+
+    const FILE: &str = r#"{
+        "bindings": {
+            "editor": { "save": [{ "modifier": "keyboard/leftctrl", "button": "keyboard/s" }] },
+            "gameplay": { "jump": ["keyboard/space", "gamepad/a"] }
+        },
+        "when": [
+            {
+                "os": "macos",
+                "bindings": {
+                    "editor": { "save": [{ "modifier": "keyboard/leftsuper", "button": "keyboard/s" }] }
+                }
+            },
+            {
+                "controller": "nintendo",
+                "bindings": { "gameplay": { "jump": ["keyboard/space", "gamepad/b"] } }
+            }
+        ]
+    }"#;
+
+    fn load(context: &BindingsContext) -> crate::Bindings {
+        let file: BindingsFile = serde_json::from_str(FILE).unwrap();
+        let (bindings, _) = file.resolve(context).migrate(&Migrations::new()).unwrap();
+        bindings
+    }
+
+    fn save_modifier(bindings: &crate::Bindings) -> Binding {
+        bindings.get("editor").unwrap().get("save").unwrap()[0]
+    }
+
+    #[test]
+    fn resolve_conditions() {
+        let context = BindingsContext {
+            os: "windows".to_owned(),
+            ..Default::default()
+        };
+        let bindings = load(&context);
+        assert_eq!(
+            save_modifier(&bindings),
+            Binding::Chord(ChordBinding {
+                modifier: KeyboardKey::LControl.into(),
+                button: KeyboardKey::S.into(),
+            })
+        );
+
+        let context = BindingsContext {
+            os: "macos".to_owned(),
+            ..Default::default()
+        }
+        .with_controller(ControllerFamily::Nintendo);
+        let bindings = load(&context);
+        assert_eq!(
+            save_modifier(&bindings),
+            Binding::Chord(ChordBinding {
+                modifier: KeyboardKey::LSuper.into(),
+                button: KeyboardKey::S.into(),
+            })
+        );
+        assert_eq!(
+            bindings.get("gameplay").unwrap().get("jump").unwrap(),
+            &[KeyboardKey::Space.into(), GamepadButton::B.into()]
+        );
+    }
+
+    #[test]
+    fn report_unresolved_conditions() {
+        let file: BindingsFile = serde_json::from_str(FILE).unwrap();
+        let (bindings, report) = file.migrate(&Migrations::new()).unwrap();
+
+        assert_eq!(
+            report.changes,
+            [MigrationChange::UnresolvedConditions {
+                file: None,
+                sections: 2,
+            }]
+        );
+        assert_eq!(
+            save_modifier(&bindings),
+            Binding::Chord(ChordBinding {
+                modifier: KeyboardKey::LControl.into(),
+                button: KeyboardKey::S.into(),
+            })
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{BTreeSet, HashSet};
+
+use crate::buttons::Input;
+
+use super::{Binding, Bindings};
+
+/// Describes which actions are allowed to share inputs when looking for
+/// conflicts with [`Bindings::conflicts`].
+///
+/// By default, every pair of action sets can be active at the same time, so
+/// any input used by two different actions is a conflict.
+#[derive(Debug, Clone, Default)]
+pub struct ConflictRules {
+    exclusive_sets: Vec<HashSet<String>>,
+    tolerated_actions: Vec<HashSet<String>>,
+}
+
+impl ConflictRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare that the given action sets are never active at the same time,
+    /// like `menu` and `gameplay`. Actions in different sets of the group can
+    /// share inputs.
+    pub fn with_exclusive_sets<I, S>(mut self, sets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclusive_sets
+            .push(sets.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Declare that the given actions, named like `gameplay/jump`, are allowed
+    /// to share inputs with each other.
+    pub fn with_tolerated_actions<I, S>(mut self, actions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tolerated_actions
+            .push(actions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Tells whether the two actions can share an input without it being
+    /// reported as a conflict.
+    pub fn allows(&self, action: &str, other: &str) -> bool {
+        if action == other {
+            return true;
+        }
+
+        let set = set_name(action);
+        let other_set = set_name(other);
+
+        let exclusive = set != other_set
+            && self
+                .exclusive_sets
+                .iter()
+                .any(|group| group.contains(set) && group.contains(other_set));
+
+        let tolerated = self
+            .tolerated_actions
+            .iter()
+            .any(|group| group.contains(action) && group.contains(other));
+
+        exclusive || tolerated
+    }
+}
+
+/// An input used by two actions that can be active at the same time.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Conflict {
+    /// The input that both actions use.
+    pub input: Input,
+
+    /// The full name of the first action, like `gameplay/jump`.
+    pub action: String,
+
+    /// The full name of the other action that uses the input.
+    pub other: String,
+}
+
+impl Bindings {
+    /// Find every input that's used by more than one action, ignoring pairs
+    /// of actions allowed by the given rules.
+    ///
+    /// Each conflicting pair is reported once per input, with `action` sorted
+    /// before `other`.
+    pub fn conflicts(&self, rules: &ConflictRules) -> Vec<Conflict> {
+        let uses = self.input_uses();
+        let mut conflicts = BTreeSet::new();
+
+        for (i, (action, input)) in uses.iter().enumerate() {
+            for (other, other_input) in &uses[i + 1..] {
+                if input == other_input && !rules.allows(action, other) {
+                    let (action, other) = if action < other {
+                        (action, other)
+                    } else {
+                        (other, action)
+                    };
+
+                    conflicts.insert(Conflict {
+                        input: *input,
+                        action: action.clone(),
+                        other: other.clone(),
+                    });
+                }
+            }
+        }
+
+        conflicts.into_iter().collect()
+    }
+
+    /// Find the conflicts that would be introduced by binding `binding` to
+    /// `action`, named like `gameplay/jump`. This is useful for warning players
+    /// while they rebind controls.
+    ///
+    /// Every returned conflict has `action` set to the given action.
+    pub fn conflicts_for(
+        &self,
+        rules: &ConflictRules,
+        action: &str,
+        binding: &Binding,
+    ) -> Vec<Conflict> {
+        let inputs = binding.inputs();
+        let mut conflicts = BTreeSet::new();
+
+        for (other, input) in self.input_uses() {
+            if inputs.contains(&input) && !rules.allows(action, &other) {
+                conflicts.insert(Conflict {
+                    input,
+                    action: action.to_owned(),
+                    other,
+                });
+            }
+        }
+
+        conflicts.into_iter().collect()
+    }
+
+    /// Lists each action's full name alongside every input it reads.
+    fn input_uses(&self) -> Vec<(String, Input)> {
+        let mut uses = BTreeSet::new();
+
+        for (set_name, set) in &self.action_sets {
+            for (action_name, bindings) in &set.actions {
+                let full_name = format!("{}/{}", set_name, action_name);
+
+                for input in bindings.iter().flat_map(Binding::inputs) {
+                    uses.insert((full_name.clone(), input));
+                }
+            }
+        }
+
+        uses.into_iter().collect()
+    }
+}
+
+fn set_name(action: &str) -> &str {
+    action.split_once('/').map_or(action, |(set, _action)| set)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ActionSetBindings, Button, GamepadButton, KeyboardKey};
+
+    use super::*;
+
+    fn button(button: impl Into<Button>) -> Binding {
+        Binding::from(button.into())
+    }
+
+    fn bindings() -> Bindings {
+        let space = button(KeyboardKey::Space);
+        let a = button(GamepadButton::A);
+
+        let mut gameplay = ActionSetBindings::new();
+        gameplay.insert("jump", vec![space, a]);
+        gameplay.insert("interact", vec![button(KeyboardKey::E), a]);
+
+        let mut menu = ActionSetBindings::new();
+        menu.insert("confirm", vec![button(KeyboardKey::Return), a]);
+
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", gameplay);
+        bindings.insert("menu", menu);
+        bindings
+    }
+
+    fn conflict(input: GamepadButton, action: &str, other: &str) -> Conflict {
+        Conflict {
+            input: Button::from(input).into(),
+            action: action.to_owned(),
+            other: other.to_owned(),
+        }
+    }
+
+    #[test]
+    fn find_conflicts() {
+        let bindings = bindings();
+
+        let conflicts = bindings.conflicts(&ConflictRules::new());
+        assert_eq!(
+            conflicts,
+            vec![
+                conflict(GamepadButton::A, "gameplay/interact", "gameplay/jump"),
+                conflict(GamepadButton::A, "gameplay/interact", "menu/confirm"),
+                conflict(GamepadButton::A, "gameplay/jump", "menu/confirm"),
+            ]
+        );
+
+        let rules = ConflictRules::new()
+            .with_exclusive_sets(["gameplay", "menu"])
+            .with_tolerated_actions(["gameplay/jump", "gameplay/interact"]);
+        assert_eq!(bindings.conflicts(&rules), vec![]);
+    }
+
+    #[test]
+    fn conflicts_for_candidate() {
+        let bindings = bindings();
+        let rules = ConflictRules::new().with_exclusive_sets(["gameplay", "menu"]);
+
+        let conflicts =
+            bindings.conflicts_for(&rules, "gameplay/crouch", &button(KeyboardKey::Space));
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                input: Button::from(KeyboardKey::Space).into(),
+                action: "gameplay/crouch".to_owned(),
+                other: "gameplay/jump".to_owned(),
+            }]
+        );
+
+        let conflicts = bindings.conflicts_for(&rules, "menu/back", &button(KeyboardKey::Space));
+        assert_eq!(conflicts, vec![]);
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeSet;
+
+use super::{Binding, Bindings};
+
+/// An action whose bindings are different between two sets of [`Bindings`].
+/// Returned by [`Bindings::diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionDiff {
+    /// The full name of the action, like `gameplay/jump`.
+    pub action: String,
+
+    /// The action's bindings before, or `None` if it wasn't defined.
+    pub old: Option<Vec<Binding>>,
+
+    /// The action's bindings after, or `None` if it isn't defined anymore.
+    pub new: Option<Vec<Binding>>,
+}
+
+impl ActionDiff {
+    /// Returns the bindings that were removed from the action.
+    pub fn removed(&self) -> Vec<Binding> {
+        missing_from(self.old.as_deref(), self.new.as_deref())
+    }
+
+    /// Returns the bindings that were added to the action.
+    pub fn added(&self) -> Vec<Binding> {
+        missing_from(self.new.as_deref(), self.old.as_deref())
+    }
+
+    /// Tells whether the action has the same bindings as before, but in a
+    /// different order.
+    pub fn is_reordered(&self) -> bool {
+        self.old.is_some()
+            && self.new.is_some()
+            && self.removed().is_empty()
+            && self.added().is_empty()
+    }
+}
+
+impl Bindings {
+    /// Compare these bindings to `other`, listing every action whose bindings
+    /// changed, sorted by name.
+    pub fn diff(&self, other: &Bindings) -> Vec<ActionDiff> {
+        let names: BTreeSet<_> = action_names(self).chain(action_names(other)).collect();
+
+        names
+            .into_iter()
+            .filter_map(|(set, action)| {
+                let old = self.get(set).and_then(|bindings| bindings.get(action));
+                let new = other.get(set).and_then(|bindings| bindings.get(action));
+
+                if old == new {
+                    return None;
+                }
+
+                Some(ActionDiff {
+                    action: format!("{}/{}", set, action),
+                    old: old.cloned(),
+                    new: new.cloned(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn action_names(bindings: &Bindings) -> impl Iterator<Item = (&str, &str)> {
+    bindings.action_sets.iter().flat_map(|(set, set_bindings)| {
+        set_bindings
+            .actions
+            .keys()
+            .map(move |action| (set.as_str(), action.as_str()))
+    })
+}
+
+/// Returns the bindings in `from` that aren't in `to`. Bindings are counted,
+/// so a binding listed twice in `from` and once in `to` is returned once.
+fn missing_from(from: Option<&[Binding]>, to: Option<&[Binding]>) -> Vec<Binding> {
+    let to = to.unwrap_or(&[]);
+    let mut matched = vec![false; to.len()];
+    let mut missing = Vec::new();
+
+    for binding in from.unwrap_or(&[]) {
+        let found = to
+            .iter()
+            .zip(&mut matched)
+            .find(|(other, matched)| !**matched && *other == binding);
+
+        match found {
+            Some((_, matched)) => *matched = true,
+            None => missing.push(*binding),
+        }
+    }
+
+    missing
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ActionSetBindings, GamepadButton, KeyboardKey};
+
+    use super::*;
+
+    fn bindings(actions: &[(&str, Vec<Binding>)]) -> Bindings {
+        let mut set = ActionSetBindings::new();
+        for (name, list) in actions {
+            set.insert(*name, list.clone());
+        }
+
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", set);
+        bindings
+    }
+
+    #[test]
+    fn diff_actions() {
+        let old = bindings(&[
+            (
+                "jump",
+                vec![KeyboardKey::Space.into(), GamepadButton::A.into()],
+            ),
+            ("crouch", vec![KeyboardKey::C.into()]),
+            (
+                "interact",
+                vec![KeyboardKey::E.into(), KeyboardKey::E.into()],
+            ),
+            (
+                "sprint",
+                vec![KeyboardKey::LShift.into(), GamepadButton::LeftThumb.into()],
+            ),
+        ]);
+        let new = bindings(&[
+            (
+                "jump",
+                vec![GamepadButton::A.into(), KeyboardKey::Space.into()],
+            ),
+            ("interact", vec![KeyboardKey::E.into()]),
+            ("sprint", vec![KeyboardKey::LShift.into()]),
+            ("walk", vec![KeyboardKey::LControl.into()]),
+        ]);
+
+        let diff = old.diff(&new);
+        let names: Vec<_> = diff.iter().map(|diff| diff.action.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "gameplay/crouch",
+                "gameplay/interact",
+                "gameplay/jump",
+                "gameplay/sprint",
+                "gameplay/walk"
+            ]
+        );
+
+        assert_eq!(diff[0].new, None);
+        assert_eq!(diff[0].removed(), [KeyboardKey::C.into()]);
+        assert_eq!(diff[1].removed(), [KeyboardKey::E.into()]);
+        assert!(!diff[1].is_reordered());
+        assert!(diff[2].is_reordered());
+        assert_eq!(diff[3].removed(), [GamepadButton::LeftThumb.into()]);
+        assert!(diff[3].added().is_empty());
+        assert_eq!(diff[4].added(), [KeyboardKey::LControl.into()]);
+
+        assert!(new.diff(&new).is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+
+use super::BindingsFile;
+
+/// Loads bindings files by name, used by [`BindingsFile::load`] to follow
+/// `extends` lists.
+///
+/// Names are passed through exactly as they're written in the file, so they
+/// can refer to paths on disk, entries in a packed asset archive, or anything
+/// else. Parsing is also up to the loader, which lets each game pick its own
+/// file format.
+pub trait BindingsLoader {
+    fn load(&self, name: &str) -> anyhow::Result<BindingsFile>;
+}
+
+impl<F> BindingsLoader for F
+where
+    F: Fn(&str) -> anyhow::Result<BindingsFile>,
+{
+    fn load(&self, name: &str) -> anyhow::Result<BindingsFile> {
+        self(name)
+    }
+}
+
+impl BindingsLoader for HashMap<String, BindingsFile> {
+    fn load(&self, name: &str) -> anyhow::Result<BindingsFile> {
+        match self.get(name) {
+            Some(file) => Ok(file.clone()),
+            None => anyhow::bail!("no bindings file named '{}'", name),
+        }
+    }
+}
+
+impl BindingsFile {
+    /// Load a bindings file along with every file it extends.
+    ///
+    /// Files listed in `extends` are applied in order underneath the file
+    /// that extends them, so later files replace actions from earlier ones
+    /// and the file itself replaces actions from all of them. Fails if any
+    /// file can't be loaded or if a file ends up extending itself.
+    pub fn load<L>(name: &str, loader: &L) -> anyhow::Result<Self>
+    where
+        L: BindingsLoader + ?Sized,
+    {
+        load_inner(name, loader, &mut Vec::new())
+    }
+}
+
+fn load_inner<L>(name: &str, loader: &L, stack: &mut Vec<String>) -> anyhow::Result<BindingsFile>
+where
+    L: BindingsLoader + ?Sized,
+{
+    if let Some(start) = stack.iter().position(|entry| entry == name) {
+        let cycle: Vec<_> = stack[start..]
+            .iter()
+            .map(String::as_str)
+            .chain([name])
+            .collect();
+
+        anyhow::bail!(
+            "bindings file '{}' extends itself: {}",
+            name,
+            cycle.join(" -> ")
+        );
+    }
+
+    let mut file = loader
+        .load(name)
+        .with_context(|| format!("failed to load bindings file '{}'", name))?;
+
+    stack.push(name.to_owned());
+
+    for parent in &file.extends {
+        let loaded = load_inner(parent, loader, stack).with_context(|| {
+            format!(
+                "in bindings file '{}', key 'extends' entry '{}'",
+                name, parent
+            )
+        })?;
+
+        file.parents.push(loaded);
+    }
+
+    stack.pop();
+    file.path = Some(name.to_owned());
+
+    Ok(file)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{GamepadButton, KeyboardKey, MigrationChange, Migrations};
+
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> HashMap<String, BindingsFile> {
+        entries
+            .iter()
+            .map(|(name, json)| (name.to_string(), serde_json::from_str(json).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn override_order() {
+        let loader = files(&[
+            (
+                "base",
+                r#"{ "bindings": { "gameplay": {
+                    "jump": ["keyboard/space"], "crouch": ["keyboard/c"], "sprint": ["keyboard/leftshift"]
+                } } }"#,
+            ),
+            (
+                "dlc",
+                r#"{ "extends": ["base"], "bindings": {
+                    "gameplay": { "crouch": ["keyboard/x"] },
+                    "vehicle": { "honk": ["keyboard/h"] }
+                } }"#,
+            ),
+            (
+                "mod",
+                r#"{ "extends": ["base", "dlc"], "bindings": { "gameplay": { "jump": ["gamepad/a"] } } }"#,
+            ),
+        ]);
+
+        let file = BindingsFile::load("mod", &loader).unwrap();
+        let (bindings, _) = file.migrate(&Migrations::new()).unwrap();
+
+        let gameplay = bindings.get("gameplay").unwrap();
+        assert_eq!(gameplay.get("jump").unwrap(), &[GamepadButton::A.into()]);
+        assert_eq!(gameplay.get("crouch").unwrap(), &[KeyboardKey::X.into()]);
+        assert_eq!(
+            gameplay.get("sprint").unwrap(),
+            &[KeyboardKey::LShift.into()]
+        );
+        assert_eq!(
+            bindings.get("vehicle").unwrap().get("honk").unwrap(),
+            &[KeyboardKey::H.into()]
+        );
+    }
+
+    #[test]
+    fn errors_name_files() {
+        let loader = files(&[
+            ("a", r#"{ "extends": ["b"], "bindings": {} }"#),
+            ("b", r#"{ "extends": ["a"], "bindings": {} }"#),
+            ("c", r#"{ "extends": ["missing"], "bindings": {} }"#),
+        ]);
+
+        let err = BindingsFile::load("a", &loader).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "bindings file 'a' extends itself: a -> b -> a"
+        );
+
+        let err = BindingsFile::load("c", &loader).unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "in bindings file 'c', key 'extends' entry 'missing': \
+             failed to load bindings file 'missing': no bindings file named 'missing'"
+        );
+    }
+
+    #[test]
+    fn dropped_bindings_name_files() {
+        let loader = files(&[
+            (
+                "base",
+                r#"{ "bindings": { "gameplay": { "jump": ["keyboard/nonsense"] } } }"#,
+            ),
+            (
+                "user",
+                r#"{ "extends": ["base"], "bindings": { "gameplay": { "crouch": ["keyboard/c"] } } }"#,
+            ),
+        ]);
+
+        let file = BindingsFile::load("user", &loader).unwrap();
+        let (_, report) = file.migrate(&Migrations::new()).unwrap();
+
+        assert_eq!(
+            report.changes,
+            [MigrationChange::DroppedBinding {
+                action: "gameplay/jump".to_owned(),
+                file: Some("base".to_owned()),
+                reason: "unknown input 'keyboard/nonsense'".to_owned(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use super::{Binding, Bindings};
+
+/// One of the layers that make up [`LayeredBindings`], from lowest to highest
+/// precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BindingLayer {
+    /// Defaults shipped with the game.
+    Engine,
+
+    /// Defaults for the platform the game is running on.
+    Platform,
+
+    /// Defaults for the kind of device the player is using.
+    Device,
+
+    /// Changes made by the player.
+    User,
+}
+
+impl BindingLayer {
+    /// Every layer, from lowest to highest precedence.
+    pub const ALL: [BindingLayer; 4] = [
+        BindingLayer::Engine,
+        BindingLayer::Platform,
+        BindingLayer::Device,
+        BindingLayer::User,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Bindings built from several layers of defaults with the player's changes on
+/// top. An action's bindings come from the highest layer that defines it, the
+/// same way that [`Bindings::merge`] replaces whole actions.
+///
+/// Only the [`BindingLayer::User`] layer needs to be saved; the other layers
+/// can be rebuilt by the game every time it starts.
+#[derive(Debug, Clone, Default)]
+pub struct LayeredBindings {
+    layers: [Bindings; 4],
+}
+
+/// An action's effective bindings, along with the layer they came from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayeredAction<'a> {
+    pub bindings: &'a [Binding],
+    pub layer: BindingLayer,
+}
+
+impl LayeredBindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the bindings defined in a single layer.
+    pub fn layer(&self, layer: BindingLayer) -> &Bindings {
+        &self.layers[layer.index()]
+    }
+
+    /// Returns the bindings defined in a single layer for editing.
+    pub fn layer_mut(&mut self, layer: BindingLayer) -> &mut Bindings {
+        &mut self.layers[layer.index()]
+    }
+
+    /// Replace every binding in a layer.
+    pub fn set_layer(&mut self, layer: BindingLayer, bindings: Bindings) {
+        self.layers[layer.index()] = bindings;
+    }
+
+    /// Set an action's bindings in the given layer.
+    pub fn insert<S, A>(&mut self, layer: BindingLayer, set: S, action: A, bindings: Vec<Binding>)
+    where
+        S: Into<String>,
+        A: Into<String>,
+    {
+        self.layer_mut(layer)
+            .action_sets
+            .entry(set.into())
+            .or_default()
+            .insert(action, bindings);
+    }
+
+    /// Returns the effective bindings of an action and the layer that they
+    /// came from.
+    pub fn get(&self, set: &str, action: &str) -> Option<LayeredAction<'_>> {
+        BindingLayer::ALL.into_iter().rev().find_map(|layer| {
+            let bindings = self.layer(layer).get(set)?.get(action)?;
+
+            Some(LayeredAction { bindings, layer })
+        })
+    }
+
+    /// Tells whether the player has changed an action's bindings.
+    pub fn is_modified(&self, set: &str, action: &str) -> bool {
+        self.layer(BindingLayer::User)
+            .get(set)
+            .is_some_and(|bindings| bindings.get(action).is_some())
+    }
+
+    /// Undo the player's changes to a single action.
+    pub fn reset_action(&mut self, set: &str, action: &str) {
+        let user = self.layer_mut(BindingLayer::User);
+
+        if let Some(bindings) = user.get_mut(set) {
+            bindings.actions.remove(action);
+
+            if bindings.actions.is_empty() {
+                user.action_sets.remove(set);
+            }
+        }
+    }
+
+    /// Undo the player's changes to every action in an action set.
+    pub fn reset_set(&mut self, set: &str) {
+        self.layer_mut(BindingLayer::User).action_sets.remove(set);
+    }
+
+    /// Undo all of the player's changes.
+    pub fn reset_all(&mut self) {
+        self.layer_mut(BindingLayer::User).clear();
+    }
+
+    /// Returns the player's changes, which is everything that needs to be
+    /// saved.
+    pub fn user(&self) -> &Bindings {
+        self.layer(BindingLayer::User)
+    }
+
+    /// Flatten every layer into the bindings that should be given to
+    /// [`Session::use_bindings`](crate::Session::use_bindings).
+    pub fn resolve(&self) -> Bindings {
+        let mut resolved = Bindings::new();
+
+        for layer in &self.layers {
+            resolved.merge(layer.clone());
+        }
+
+        resolved
+    }
+
+    /// Lists every action defined in any layer as `(set, action)` pairs,
+    /// sorted by name.
+    pub fn actions(&self) -> Vec<(&str, &str)> {
+        let mut actions: Vec<_> = self
+            .layers
+            .iter()
+            .flat_map(|layer| &layer.action_sets)
+            .flat_map(|(set, bindings)| {
+                bindings
+                    .actions
+                    .keys()
+                    .map(move |action| (set.as_str(), action.as_str()))
+            })
+            .collect();
+
+        actions.sort_unstable();
+        actions.dedup();
+        actions
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{GamepadButton, KeyboardKey};
+
+    use super::*;
+
+    fn layered() -> LayeredBindings {
+        let mut layered = LayeredBindings::new();
+        layered.insert(
+            BindingLayer::Engine,
+            "gameplay",
+            "jump",
+            vec![KeyboardKey::Space.into()],
+        );
+        layered.insert(
+            BindingLayer::Engine,
+            "gameplay",
+            "crouch",
+            vec![KeyboardKey::C.into()],
+        );
+        layered.insert(
+            BindingLayer::Device,
+            "gameplay",
+            "jump",
+            vec![GamepadButton::A.into()],
+        );
+        layered.insert(
+            BindingLayer::User,
+            "gameplay",
+            "crouch",
+            vec![KeyboardKey::LControl.into()],
+        );
+        layered
+    }
+
+    #[test]
+    fn provenance() {
+        let layered = layered();
+
+        let jump = layered.get("gameplay", "jump").unwrap();
+        assert_eq!(jump.layer, BindingLayer::Device);
+        assert_eq!(jump.bindings, [GamepadButton::A.into()]);
+        assert!(!layered.is_modified("gameplay", "jump"));
+
+        let crouch = layered.get("gameplay", "crouch").unwrap();
+        assert_eq!(crouch.layer, BindingLayer::User);
+        assert!(layered.is_modified("gameplay", "crouch"));
+
+        assert_eq!(
+            layered.actions(),
+            [("gameplay", "crouch"), ("gameplay", "jump")]
+        );
+
+        let resolved = layered.resolve();
+        let gameplay = resolved.get("gameplay").unwrap();
+        assert_eq!(gameplay.get("jump").unwrap(), &[GamepadButton::A.into()]);
+        assert_eq!(
+            gameplay.get("crouch").unwrap(),
+            &[KeyboardKey::LControl.into()]
+        );
+    }
+
+    #[test]
+    fn reset_and_export() {
+        let mut layered = layered();
+
+        let user = serde_json::to_string(layered.user()).unwrap();
+        assert_eq!(user, r#"{"gameplay":{"crouch":["keyboard/leftctrl"]}}"#);
+
+        layered.reset_action("gameplay", "crouch");
+        assert_eq!(
+            layered.get("gameplay", "crouch").unwrap().layer,
+            BindingLayer::Engine
+        );
+        assert!(layered.user().action_sets.is_empty());
+
+        layered.insert(
+            BindingLayer::User,
+            "gameplay",
+            "jump",
+            vec![KeyboardKey::J.into()],
+        );
+        layered.reset_set("gameplay");
+        assert!(!layered.is_modified("gameplay", "jump"));
+    }
+}
@@ -0,0 +1,733 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::conditions::{BindingsContext, ConditionalBlock};
+use super::repr::BindingRepr;
+use super::{ActionSetBindings, Bindings};
+
+pub(super) type RawBindings = BTreeMap<String, BTreeMap<String, Vec<BindingRepr>>>;
+
+/// A bindings file with a version number, so that bindings saved by an older
+/// version of a game can be upgraded with [`Migrations`].
+///
+/// Serialized as a map with `version` and `bindings` keys. Files without a
+/// `version` are treated as version 0.
+///
+/// Files can also have a `when` list of sections that only apply on some
+/// platforms or with some controllers, which are applied with
+/// [`BindingsFile::resolve`], and an `extends` list of other files to build
+/// on, which are loaded by [`BindingsFile::load`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingsFile {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+    pub(super) bindings: RawBindings,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) when: Vec<ConditionalBlock>,
+
+    /// Where this file was loaded from, if it was loaded with
+    /// [`BindingsFile::load`].
+    #[serde(skip)]
+    pub(super) path: Option<String>,
+
+    /// The files listed in `extends`, once they've been loaded.
+    #[serde(skip)]
+    pub(super) parents: Vec<BindingsFile>,
+}
+
+impl BindingsFile {
+    /// Create a file holding the given bindings, marked with the given version.
+    /// This is usually [`Migrations::current_version`].
+    pub fn new(version: u32, bindings: &Bindings) -> Self {
+        Self {
+            version,
+            extends: Vec::new(),
+            bindings: raw_bindings(bindings),
+            when: Vec::new(),
+            path: None,
+            parents: Vec::new(),
+        }
+    }
+
+    /// Apply every conditional section that matches the given context, in
+    /// the order they're listed. Actions in a matching section replace the
+    /// same actions from earlier in the file.
+    ///
+    /// Files that this file extends are resolved too.
+    pub fn resolve(mut self, context: &BindingsContext) -> Self {
+        for block in std::mem::take(&mut self.when) {
+            if block.matches(context) {
+                merge_raw(&mut self.bindings, block.bindings);
+            }
+        }
+
+        self.parents = self
+            .parents
+            .into_iter()
+            .map(|parent| parent.resolve(context))
+            .collect();
+
+        self
+    }
+
+    /// Upgrade the file to the current version and parse its bindings.
+    ///
+    /// Bindings that use removed inputs or can't be parsed anymore are dropped
+    /// and listed in the report instead of failing the whole file. Fails if
+    /// the file is newer than `migrations` knows about.
+    ///
+    /// Conditional sections only apply if [`BindingsFile::resolve`] was called
+    /// first. Otherwise they're left out, which is recorded in the report.
+    ///
+    /// Each file that this file extends is upgraded from its own version
+    /// before being merged underneath this file.
+    pub fn migrate(self, migrations: &Migrations) -> anyhow::Result<(Bindings, MigrationReport)> {
+        let mut report = MigrationReport {
+            from_version: self.version,
+            to_version: migrations.current_version(),
+            changes: Vec::new(),
+        };
+
+        let bindings = self.upgrade(migrations, &mut report.changes)?;
+
+        Ok((bindings, report))
+    }
+
+    /// Parse the file's bindings without migrating them, failing on the first
+    /// binding that can't be parsed. This is meant for checking files that
+    /// ship with a game, where every binding should be valid.
+    ///
+    /// Files that this file extends are parsed and merged underneath it.
+    /// Conditional sections that haven't been applied with
+    /// [`BindingsFile::resolve`] are checked but not applied.
+    pub fn into_bindings(self) -> anyhow::Result<Bindings> {
+        let file = match &self.path {
+            Some(path) => format!("bindings file '{}'", path),
+            None => "bindings file".to_owned(),
+        };
+
+        let mut bindings = Bindings::new();
+
+        for parent in self.parents {
+            bindings.merge(parent.into_bindings()?);
+        }
+
+        for (index, block) in self.when.into_iter().enumerate() {
+            parse_strict(block.bindings, |key| {
+                format!("in {}, key 'when[{}].{}'", file, index, key)
+            })?;
+        }
+
+        bindings.merge(parse_strict(self.bindings, |key| {
+            format!("in {}, key '{}'", file, key)
+        })?);
+
+        Ok(bindings)
+    }
+
+    /// Apply migrations to this file and every file it extends and parse them
+    /// leniently, then merge them together with this file on top.
+    fn upgrade(
+        self,
+        migrations: &Migrations,
+        changes: &mut Vec<MigrationChange>,
+    ) -> anyhow::Result<Bindings> {
+        let to_version = migrations.current_version();
+        if self.version > to_version {
+            match &self.path {
+                Some(path) => anyhow::bail!(
+                    "bindings file '{}' is version {}, but only versions up to {} are supported",
+                    path,
+                    self.version,
+                    to_version
+                ),
+                None => anyhow::bail!(
+                    "bindings file is version {}, but only versions up to {} are supported",
+                    self.version,
+                    to_version
+                ),
+            }
+        }
+
+        if !self.when.is_empty() {
+            changes.push(MigrationChange::UnresolvedConditions {
+                file: self.path.clone(),
+                sections: self.when.len(),
+            });
+        }
+
+        let mut bindings = Bindings::new();
+
+        for parent in self.parents {
+            bindings.merge(parent.upgrade(migrations, changes)?);
+        }
+
+        let mut own = self.bindings;
+
+        for step in &migrations.steps {
+            if step.version > self.version {
+                step.change.apply(&mut own, self.path.as_deref(), changes);
+            }
+        }
+
+        bindings.merge(parse_lenient(own, self.path.as_deref(), changes));
+        Ok(bindings)
+    }
+}
+
+/// Parse every binding, dropping the ones that can't be parsed and recording
+/// them as coming from `file`.
+fn parse_lenient(
+    raw: RawBindings,
+    file: Option<&str>,
+    changes: &mut Vec<MigrationChange>,
+) -> Bindings {
+    let mut bindings = Bindings::new();
+
+    for (set_name, actions) in raw {
+        let mut set = ActionSetBindings::new();
+
+        for (action_name, list) in actions {
+            let full_name = format!("{}/{}", set_name, action_name);
+            let mut parsed = Vec::new();
+
+            for repr in list {
+                match repr.into_binding() {
+                    Ok(binding) => parsed.push(binding),
+                    Err(err) => changes.push(MigrationChange::DroppedBinding {
+                        action: full_name.clone(),
+                        file: file.map(str::to_owned),
+                        reason: format!("{:#}", err),
+                    }),
+                }
+            }
+
+            set.insert(action_name, parsed);
+        }
+
+        bindings.insert(set_name, set);
+    }
+
+    bindings
+}
+
+/// Parse every binding, failing with the context given by `describe` for the
+/// first action with a binding that can't be parsed.
+fn parse_strict<F>(raw: RawBindings, describe: F) -> anyhow::Result<Bindings>
+where
+    F: Fn(&str) -> String,
+{
+    let mut bindings = Bindings::new();
+
+    for (set_name, actions) in raw {
+        let mut set = ActionSetBindings::new();
+
+        for (action_name, list) in actions {
+            let parsed = list
+                .into_iter()
+                .map(BindingRepr::into_binding)
+                .collect::<anyhow::Result<Vec<_>>>()
+                .with_context(|| describe(&format!("{}/{}", set_name, action_name)))?;
+
+            set.insert(action_name, parsed);
+        }
+
+        bindings.insert(set_name, set);
+    }
+
+    Ok(bindings)
+}
+
+/// Convert bindings to their serialized form.
+pub(super) fn raw_bindings(bindings: &Bindings) -> RawBindings {
+    bindings
+        .action_sets
+        .iter()
+        .map(|(set_name, set)| {
+            let actions = set
+                .actions
+                .iter()
+                .map(|(action_name, list)| {
+                    (
+                        action_name.clone(),
+                        list.iter().map(BindingRepr::from_binding).collect(),
+                    )
+                })
+                .collect();
+
+            (set_name.clone(), actions)
+        })
+        .collect()
+}
+
+/// Merge `other` into `raw`, replacing any actions defined in both.
+fn merge_raw(raw: &mut RawBindings, other: RawBindings) {
+    for (set_name, actions) in other {
+        raw.entry(set_name).or_default().extend(actions);
+    }
+}
+
+/// A list of renames and removals made between versions of a game's
+/// bindings, used by [`BindingsFile::migrate`].
+///
+/// Every change is tagged with the version that introduced it. Migrating a
+/// file applies every change newer than the file, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct Migrations {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    version: u32,
+    change: Change,
+}
+
+#[derive(Debug, Clone)]
+enum Change {
+    RenameSet { from: String, to: String },
+    RenameAction { from: String, to: String },
+    RenameInput { from: String, to: String },
+    RemoveInput { name: String },
+}
+
+impl Migrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rename an action set. If a set with the new name already exists, the
+    /// renamed set's actions are merged into it.
+    pub fn rename_set<F, T>(self, version: u32, from: F, to: T) -> Self
+    where
+        F: Into<String>,
+        T: Into<String>,
+    {
+        self.with_change(
+            version,
+            Change::RenameSet {
+                from: from.into(),
+                to: to.into(),
+            },
+        )
+    }
+
+    /// Rename an action, which can also move it to another set. Actions are
+    /// named by their full name, like `gameplay/attack`.
+    ///
+    /// If an action with the new name already exists, the renamed action's
+    /// bindings are added after its bindings.
+    ///
+    /// # Panics
+    /// Panics if either name isn't a full name with a `/`.
+    pub fn rename_action<F, T>(self, version: u32, from: F, to: T) -> Self
+    where
+        F: Into<String>,
+        T: Into<String>,
+    {
+        let from = from.into();
+        let to = to.into();
+
+        for name in [&from, &to] {
+            assert!(
+                name.contains('/'),
+                "action names must be full names like 'set/action', but '{}' was given",
+                name
+            );
+        }
+
+        self.with_change(version, Change::RenameAction { from, to })
+    }
+
+    /// Rename an input, like `keyboard/lshift` to `keyboard/leftshift`.
+    pub fn rename_input<F, T>(self, version: u32, from: F, to: T) -> Self
+    where
+        F: Into<String>,
+        T: Into<String>,
+    {
+        self.with_change(
+            version,
+            Change::RenameInput {
+                from: from.into(),
+                to: to.into(),
+            },
+        )
+    }
+
+    /// Remove an input, dropping every binding that uses it.
+    pub fn remove_input<S: Into<String>>(self, version: u32, name: S) -> Self {
+        self.with_change(version, Change::RemoveInput { name: name.into() })
+    }
+
+    /// Returns the newest version that any change was made in, or 0 if there
+    /// are no changes.
+    pub fn current_version(&self) -> u32 {
+        self.steps
+            .iter()
+            .map(|step| step.version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn with_change(mut self, version: u32, change: Change) -> Self {
+        // Keep changes sorted by version, and in the order they were given
+        // within a version.
+        let index = self.steps.partition_point(|step| step.version <= version);
+        self.steps.insert(index, Step { version, change });
+        self
+    }
+}
+
+/// Describes what happened while migrating a [`BindingsFile`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub changes: Vec<MigrationChange>,
+}
+
+/// A single change made while migrating a [`BindingsFile`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationChange {
+    RenamedSet {
+        from: String,
+        to: String,
+    },
+    RenamedAction {
+        from: String,
+        to: String,
+    },
+    /// An action was renamed to the name of an action that already existed,
+    /// so its bindings were added to that action's bindings.
+    MergedAction {
+        from: String,
+        to: String,
+    },
+    RenamedInput {
+        action: String,
+        from: String,
+        to: String,
+    },
+    /// The file had conditional sections that weren't applied with
+    /// [`BindingsFile::resolve`] before migrating, so their bindings were left
+    /// out.
+    UnresolvedConditions {
+        /// The file's path, if it was loaded with [`BindingsFile::load`].
+        file: Option<String>,
+        sections: usize,
+    },
+    /// A binding was dropped, either because it used a removed input or
+    /// because it couldn't be parsed after migrating.
+    DroppedBinding {
+        action: String,
+        /// The path of the file the binding was in, if it was loaded with
+        /// [`BindingsFile::load`].
+        file: Option<String>,
+        reason: String,
+    },
+}
+
+impl Change {
+    fn apply(&self, raw: &mut RawBindings, file: Option<&str>, changes: &mut Vec<MigrationChange>) {
+        match self {
+            Change::RenameSet { from, to } => {
+                if let Some(actions) = raw.remove(from) {
+                    changes.push(MigrationChange::RenamedSet {
+                        from: from.clone(),
+                        to: to.clone(),
+                    });
+
+                    for (action, list) in actions {
+                        merge_action(raw, (from, &action), (to, &action), list, changes);
+                    }
+                }
+            }
+            Change::RenameAction { from, to } => {
+                let (Some((from_set, from_action)), Some((to_set, to_action))) =
+                    (from.split_once('/'), to.split_once('/'))
+                else {
+                    unreachable!("action names are checked in Migrations::rename_action");
+                };
+
+                let Some(list) = raw
+                    .get_mut(from_set)
+                    .and_then(|actions| actions.remove(from_action))
+                else {
+                    return;
+                };
+
+                if raw.get(from_set).is_some_and(BTreeMap::is_empty) {
+                    raw.remove(from_set);
+                }
+
+                if !merge_action(
+                    raw,
+                    (from_set, from_action),
+                    (to_set, to_action),
+                    list,
+                    changes,
+                ) {
+                    changes.push(MigrationChange::RenamedAction {
+                        from: from.clone(),
+                        to: to.clone(),
+                    });
+                }
+            }
+            Change::RenameInput { from, to } => {
+                for_each_binding(raw, |action, repr| {
+                    for input in repr.inputs_mut() {
+                        if input == from {
+                            input.clone_from(to);
+                            changes.push(MigrationChange::RenamedInput {
+                                action: action.to_owned(),
+                                from: from.clone(),
+                                to: to.clone(),
+                            });
+                        }
+                    }
+
+                    true
+                });
+            }
+            Change::RemoveInput { name } => {
+                for_each_binding(raw, |action, repr| {
+                    let uses_input = repr.inputs_mut().into_iter().any(|input| input == name);
+
+                    if uses_input {
+                        changes.push(MigrationChange::DroppedBinding {
+                            action: action.to_owned(),
+                            file: file.map(str::to_owned),
+                            reason: format!("input '{}' was removed", name),
+                        });
+                    }
+
+                    !uses_input
+                });
+            }
+        }
+    }
+}
+
+/// Move an action's bindings to a new name. If an action with that name already
+/// exists, the bindings are added after its own, skipping ones it already has,
+/// and the merge is recorded. Returns whether the action was merged.
+fn merge_action(
+    raw: &mut RawBindings,
+    (from_set, from_action): (&str, &str),
+    (to_set, to_action): (&str, &str),
+    list: Vec<BindingRepr>,
+    changes: &mut Vec<MigrationChange>,
+) -> bool {
+    let actions = raw.entry(to_set.to_owned()).or_default();
+
+    match actions.get_mut(to_action) {
+        Some(existing) => {
+            for repr in list {
+                if !existing.contains(&repr) {
+                    existing.push(repr);
+                }
+            }
+
+            changes.push(MigrationChange::MergedAction {
+                from: format!("{}/{}", from_set, from_action),
+                to: format!("{}/{}", to_set, to_action),
+            });
+
+            true
+        }
+        None => {
+            actions.insert(to_action.to_owned(), list);
+            false
+        }
+    }
+}
+
+/// Visit every binding with its action's full name, keeping only the bindings
+/// that `f` returns true for.
+fn for_each_binding<F>(raw: &mut RawBindings, mut f: F)
+where
+    F: FnMut(&str, &mut BindingRepr) -> bool,
+{
+    for (set_name, actions) in raw {
+        for (action_name, list) in actions {
+            let full_name = format!("{}/{}", set_name, action_name);
+            list.retain_mut(|repr| f(&full_name, repr));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Binding, GamepadButton, KeyboardKey, MouseButton};
+
+    use super::*;
+
+    fn migrations() -> Migrations {
+        Migrations::new()
+            .rename_input(2, "keyboard/lshift", "keyboard/leftshift")
+            .remove_input(2, "gamepad/guide")
+            .rename_action(1, "gameplay/attack", "combat/primary_fire")
+            .rename_set(2, "ui", "menu")
+    }
+
+    #[test]
+    fn migrate_old_file() {
+        let json = r#"{
+            "bindings": {
+                "gameplay": {
+                    "attack": ["mouse/button1", "gamepad/guide"],
+                    "sprint": ["keyboard/lshift", "keyboard/nonsense"]
+                },
+                "ui": { "back": ["keyboard/escape"] }
+            }
+        }"#;
+
+        let file: BindingsFile = serde_json::from_str(json).unwrap();
+        let (bindings, report) = file.migrate(&migrations()).unwrap();
+
+        assert_eq!(report.from_version, 0);
+        assert_eq!(report.to_version, 2);
+        assert_eq!(
+            report.changes,
+            vec![
+                MigrationChange::RenamedAction {
+                    from: "gameplay/attack".to_owned(),
+                    to: "combat/primary_fire".to_owned(),
+                },
+                MigrationChange::RenamedInput {
+                    action: "gameplay/sprint".to_owned(),
+                    from: "keyboard/lshift".to_owned(),
+                    to: "keyboard/leftshift".to_owned(),
+                },
+                MigrationChange::DroppedBinding {
+                    action: "combat/primary_fire".to_owned(),
+                    file: None,
+                    reason: "input 'gamepad/guide' was removed".to_owned(),
+                },
+                MigrationChange::RenamedSet {
+                    from: "ui".to_owned(),
+                    to: "menu".to_owned(),
+                },
+                MigrationChange::DroppedBinding {
+                    action: "gameplay/sprint".to_owned(),
+                    file: None,
+                    reason: "unknown input 'keyboard/nonsense'".to_owned(),
+                },
+            ]
+        );
+
+        let sprint = bindings.get("gameplay").unwrap().get("sprint").unwrap();
+        assert_eq!(sprint, &[Binding::from(KeyboardKey::LShift)]);
+        assert!(bindings.get("ui").is_none());
+        assert!(bindings.get("menu").unwrap().get("back").is_some());
+        assert!(bindings
+            .get("combat")
+            .unwrap()
+            .get("primary_fire")
+            .is_some());
+    }
+
+    #[test]
+    fn save_and_reload() {
+        let mut gameplay = ActionSetBindings::new();
+        gameplay.insert("jump", vec![GamepadButton::A.into()]);
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", gameplay);
+
+        let migrations = migrations();
+        let file = BindingsFile::new(migrations.current_version(), &bindings);
+        let json = serde_json::to_string(&file).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":2,"bindings":{"gameplay":{"jump":["gamepad/a"]}}}"#
+        );
+
+        let file: BindingsFile = serde_json::from_str(&json).unwrap();
+        let (reloaded, report) = file.migrate(&migrations).unwrap();
+        assert!(report.changes.is_empty());
+        assert_eq!(
+            reloaded.get("gameplay").unwrap().get("jump").unwrap(),
+            &[GamepadButton::A.into()]
+        );
+
+        let newer: BindingsFile = serde_json::from_str(r#"{"version":3,"bindings":{}}"#).unwrap();
+        assert!(newer.migrate(&migrations).is_err());
+    }
+
+    #[test]
+    fn strict_parse() {
+        let json = r#"{
+            "bindings": { "gameplay": { "jump": ["keyboard/space"] } },
+            "when": [{ "os": "macos", "bindings": { "ui": { "back": ["keyboard/nonsense"] } } }]
+        }"#;
+
+        let file: BindingsFile = serde_json::from_str(json).unwrap();
+        let err = file.clone().into_bindings().unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "in bindings file, key 'when[0].ui/back': unknown input 'keyboard/nonsense'"
+        );
+
+        let mut file = file;
+        file.when.clear();
+        let bindings = file.into_bindings().unwrap();
+        assert_eq!(
+            bindings.get("gameplay").unwrap().get("jump").unwrap(),
+            &[KeyboardKey::Space.into()]
+        );
+    }
+
+    #[test]
+    fn rename_into_existing() {
+        let json = r#"{
+            "bindings": {
+                "gameplay": { "attack": ["mouse/button1"], "fire": ["keyboard/f", "mouse/button1"] },
+                "ui": { "back": ["keyboard/escape"] },
+                "menu": { "back": ["gamepad/b"] }
+            }
+        }"#;
+
+        let migrations = Migrations::new()
+            .rename_action(1, "gameplay/fire", "gameplay/attack")
+            .rename_set(1, "ui", "menu");
+
+        let file: BindingsFile = serde_json::from_str(json).unwrap();
+        let (bindings, report) = file.migrate(&migrations).unwrap();
+
+        assert_eq!(
+            report.changes,
+            vec![
+                MigrationChange::MergedAction {
+                    from: "gameplay/fire".to_owned(),
+                    to: "gameplay/attack".to_owned(),
+                },
+                MigrationChange::RenamedSet {
+                    from: "ui".to_owned(),
+                    to: "menu".to_owned(),
+                },
+                MigrationChange::MergedAction {
+                    from: "ui/back".to_owned(),
+                    to: "menu/back".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            bindings.get("gameplay").unwrap().get("attack").unwrap(),
+            &[MouseButton::Button1.into(), KeyboardKey::F.into()]
+        );
+        assert_eq!(
+            bindings.get("menu").unwrap().get("back").unwrap(),
+            &[GamepadButton::B.into(), KeyboardKey::Escape.into()]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "full names")]
+    fn rename_needs_full_names() {
+        let _ = Migrations::new().rename_action(1, "attack", "gameplay/attack");
+    }
+}
@@ -1,22 +1,49 @@
 mod binding;
-
-use std::collections::HashMap;
+#[cfg(feature = "json")]
+mod canonical;
+mod conditions;
+mod conflicts;
+mod diff;
+mod include;
+mod layered;
+mod migration;
+mod patch;
+mod reference;
+mod repr;
+#[cfg(feature = "json")]
+mod schema;
+mod slotted;
+
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
 pub use binding::*;
+pub use conditions::BindingsContext;
+pub use conflicts::*;
+pub use diff::*;
+pub use include::*;
+pub use layered::*;
+pub use migration::*;
+pub use patch::*;
+pub use reference::*;
+pub use slotted::*;
 
 /// Defines how inputs should be mapped to actions.
-#[derive(Debug, Default, Serialize, Deserialize)]
+///
+/// Action sets and actions are kept sorted by name, so the same bindings
+/// always serialize the same way. Enable the `json` feature for
+/// `Bindings::to_canonical_json`, a layout meant for diffing.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct Bindings {
-    pub action_sets: HashMap<String, ActionSetBindings>,
+    pub action_sets: BTreeMap<String, ActionSetBindings>,
 }
 
 impl Bindings {
     pub fn new() -> Self {
         Self {
-            action_sets: HashMap::new(),
+            action_sets: BTreeMap::new(),
         }
     }
 
@@ -39,10 +66,7 @@ impl Bindings {
     /// of default bindings.
     pub fn merge(&mut self, other: Bindings) {
         for (name, action_set) in other.action_sets {
-            let bindings = self
-                .action_sets
-                .entry(name)
-                .or_insert_with(ActionSetBindings::new);
+            let bindings = self.action_sets.entry(name).or_default();
             bindings.merge(action_set);
         }
     }
@@ -52,16 +76,16 @@ impl Bindings {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ActionSetBindings {
-    pub actions: HashMap<String, Vec<Binding>>,
+    pub actions: BTreeMap<String, Vec<Binding>>,
 }
 
 impl ActionSetBindings {
     pub fn new() -> Self {
         Self {
-            actions: HashMap::new(),
+            actions: BTreeMap::new(),
         }
     }
 
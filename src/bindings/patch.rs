@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Binding, Bindings};
+
+/// A list of changes to apply on top of a set of [`Bindings`], like the
+/// changes a player made to the default bindings.
+///
+/// Unlike [`Bindings::merge`], patches can change a single binding without
+/// replacing every other binding of the action.
+///
+/// Patches are serialized as a list of operations:
+///
+/// ```json
+/// [
+///     { "op": "add", "action": "gameplay/jump", "binding": "keyboard/j" },
+///     { "op": "replace", "action": "gameplay/crouch", "slot": 0, "binding": "keyboard/c" },
+///     { "op": "unbind", "action": "gameplay/sprint" }
+/// ]
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BindingsPatch {
+    pub ops: Vec<PatchOp>,
+}
+
+/// A single change in a [`BindingsPatch`]. Actions are named by their full
+/// name, like `gameplay/jump`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchOp {
+    /// Add a binding to the end of an action's bindings.
+    Add { action: String, binding: Binding },
+
+    /// Remove a binding from an action.
+    Remove { action: String, binding: Binding },
+
+    /// Replace the binding in the given slot of an action. Replacing the slot
+    /// just past the last binding adds a new binding.
+    Replace {
+        action: String,
+        slot: usize,
+        binding: Binding,
+    },
+
+    /// Remove every binding from an action.
+    Unbind { action: String },
+}
+
+impl BindingsPatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a binding to an action.
+    pub fn add<S: Into<String>>(&mut self, action: S, binding: Binding) {
+        self.ops.push(PatchOp::Add {
+            action: action.into(),
+            binding,
+        });
+    }
+
+    /// Remove a binding from an action.
+    pub fn remove<S: Into<String>>(&mut self, action: S, binding: Binding) {
+        self.ops.push(PatchOp::Remove {
+            action: action.into(),
+            binding,
+        });
+    }
+
+    /// Replace the binding in the given slot of an action.
+    pub fn replace<S: Into<String>>(&mut self, action: S, slot: usize, binding: Binding) {
+        self.ops.push(PatchOp::Replace {
+            action: action.into(),
+            slot,
+            binding,
+        });
+    }
+
+    /// Remove every binding from an action.
+    pub fn unbind<S: Into<String>>(&mut self, action: S) {
+        self.ops.push(PatchOp::Unbind {
+            action: action.into(),
+        });
+    }
+
+    /// Apply every operation to the given bindings, in order.
+    ///
+    /// Operations that don't fit the bindings are skipped and returned, like
+    /// removing a binding that isn't there or replacing a slot that's out of
+    /// range. This usually means the defaults changed since the patch was made.
+    pub fn apply(&self, bindings: &mut Bindings) -> Vec<&PatchOp> {
+        self.ops.iter().filter(|op| !op.apply(bindings)).collect()
+    }
+}
+
+impl PatchOp {
+    /// Returns the full name of the action this operation changes.
+    pub fn action(&self) -> &str {
+        match self {
+            Self::Add { action, .. }
+            | Self::Remove { action, .. }
+            | Self::Replace { action, .. }
+            | Self::Unbind { action } => action,
+        }
+    }
+
+    /// Apply this operation, returning whether it changed anything.
+    fn apply(&self, bindings: &mut Bindings) -> bool {
+        let Some((set_name, action_name)) = self.action().split_once('/') else {
+            return false;
+        };
+
+        match self {
+            Self::Add { binding, .. } => {
+                let list = entry(bindings, set_name, action_name);
+
+                if list.contains(binding) {
+                    false
+                } else {
+                    list.push(*binding);
+                    true
+                }
+            }
+            Self::Remove { binding, .. } => {
+                let Some(list) = bindings
+                    .get_mut(set_name)
+                    .and_then(|set| set.get_mut(action_name))
+                else {
+                    return false;
+                };
+
+                match list.iter().position(|b| b == binding) {
+                    Some(index) => {
+                        list.remove(index);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Self::Replace { slot, binding, .. } => {
+                let existing = bindings
+                    .get_mut(set_name)
+                    .and_then(|set| set.get_mut(action_name));
+                let len = existing.as_ref().map_or(0, |list| list.len());
+
+                if let Some(list) = existing.filter(|_| *slot < len) {
+                    list[*slot] = *binding;
+                    true
+                } else if *slot == len {
+                    // Only create the action when the binding is actually
+                    // added, so skipped operations don't leave empty actions.
+                    entry(bindings, set_name, action_name).push(*binding);
+                    true
+                } else {
+                    false
+                }
+            }
+            Self::Unbind { .. } => {
+                entry(bindings, set_name, action_name).clear();
+                true
+            }
+        }
+    }
+}
+
+/// Returns an action's bindings, creating the action if needed.
+fn entry<'a>(
+    bindings: &'a mut Bindings,
+    set_name: &str,
+    action_name: &str,
+) -> &'a mut Vec<Binding> {
+    bindings
+        .action_sets
+        .entry(set_name.to_owned())
+        .or_default()
+        .actions
+        .entry(action_name.to_owned())
+        .or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ActionSetBindings, GamepadButton, KeyboardKey};
+
+    use super::*;
+
+    fn base() -> Bindings {
+        let mut gameplay = ActionSetBindings::new();
+        gameplay.insert(
+            "jump",
+            vec![KeyboardKey::Space.into(), GamepadButton::A.into()],
+        );
+        gameplay.insert("crouch", vec![KeyboardKey::C.into()]);
+        gameplay.insert("sprint", vec![KeyboardKey::LShift.into()]);
+
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", gameplay);
+        bindings
+    }
+
+    #[test]
+    fn apply_ops() {
+        let mut patch = BindingsPatch::new();
+        patch.add("gameplay/jump", KeyboardKey::J.into());
+        patch.remove("gameplay/jump", GamepadButton::A.into());
+        patch.replace("gameplay/crouch", 0, KeyboardKey::LControl.into());
+        patch.unbind("gameplay/sprint");
+        patch.remove("gameplay/crouch", KeyboardKey::X.into());
+        patch.replace("gameplay/crouch", 5, KeyboardKey::X.into());
+        patch.replace("menu/back", 1, KeyboardKey::Escape.into());
+
+        let mut bindings = base();
+        let skipped = patch.apply(&mut bindings);
+        assert_eq!(skipped, [&patch.ops[4], &patch.ops[5], &patch.ops[6]]);
+        assert!(bindings.get("menu").is_none());
+
+        let gameplay = bindings.get("gameplay").unwrap();
+        assert_eq!(
+            gameplay.get("jump").unwrap(),
+            &[KeyboardKey::Space.into(), KeyboardKey::J.into()]
+        );
+        assert_eq!(
+            gameplay.get("crouch").unwrap(),
+            &[KeyboardKey::LControl.into()]
+        );
+        assert_eq!(gameplay.get("sprint").unwrap(), &[]);
+    }
+
+    #[test]
+    fn serialize() {
+        let mut patch = BindingsPatch::new();
+        patch.add("gameplay/jump", KeyboardKey::J.into());
+        patch.replace("gameplay/crouch", 1, GamepadButton::B.into());
+        patch.unbind("gameplay/sprint");
+
+        let json = serde_json::to_string(&patch).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"[{"op":"add","action":"gameplay/jump","binding":"keyboard/j"},"#,
+                r#"{"op":"replace","action":"gameplay/crouch","slot":1,"binding":"gamepad/b"},"#,
+                r#"{"op":"unbind","action":"gameplay/sprint"}]"#
+            )
+        );
+
+        let parsed: BindingsPatch = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, patch);
+    }
+}
@@ -0,0 +1,577 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::{ControllerFamily, DeviceClass, DisplayNames, Input};
+
+use super::{
+    Axis1dBinding, Axis2dBinding, Axis2dDirection, Axis3dBinding, AxisDirection, Binding, Bindings,
+    ThresholdSource,
+};
+
+/// Renders a table of controls for a game's manual or store page, grouped by
+/// action set.
+///
+/// Actions are listed by name unless they're given an [`ActionDisplay`], and
+/// inputs are shown with friendly labels like "Left Shift", or with names from
+/// a [`DisplayNames`] table. Unless the reference is limited to one device
+/// with [`ControlsReference::with_device`], gamepad inputs are prefixed with
+/// "Gamepad" so that they can't be mistaken for keys.
+#[derive(Debug, Clone)]
+pub struct ControlsReference<'a> {
+    bindings: &'a Bindings,
+    actions: HashMap<String, ActionDisplay>,
+    sets: HashMap<String, String>,
+    device: Option<DeviceClass>,
+    names: &'a DisplayNames,
+    family: ControllerFamily,
+}
+
+/// How an action is shown in a [`ControlsReference`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionDisplay {
+    /// The name shown for the action, like "Jump".
+    pub label: String,
+
+    /// A longer explanation of what the action does.
+    pub description: Option<String>,
+
+    /// Whether to leave the action out, like for debug actions.
+    pub hidden: bool,
+}
+
+/// The output format of a [`ControlsReference`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReferenceFormat {
+    Markdown,
+    Html,
+    Text,
+}
+
+static DEFAULT_NAMES: DisplayNames = DisplayNames::new();
+
+struct Group {
+    title: String,
+    rows: Vec<Row>,
+}
+
+struct Row {
+    action: String,
+    controls: Vec<String>,
+    description: Option<String>,
+}
+
+impl ActionDisplay {
+    pub fn new<S: Into<String>>(label: S) -> Self {
+        Self {
+            label: label.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_description<S: Into<String>>(self, description: S) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    pub fn hidden(self) -> Self {
+        Self {
+            hidden: true,
+            ..self
+        }
+    }
+}
+
+impl<'a> ControlsReference<'a> {
+    pub fn new(bindings: &'a Bindings) -> Self {
+        Self {
+            bindings,
+            actions: HashMap::new(),
+            sets: HashMap::new(),
+            device: None,
+            names: &DEFAULT_NAMES,
+            family: ControllerFamily::Generic,
+        }
+    }
+
+    /// Set how an action is shown, using its full name like `gameplay/jump`.
+    pub fn with_action<S: Into<String>>(mut self, action: S, display: ActionDisplay) -> Self {
+        self.actions.insert(action.into(), display);
+        self
+    }
+
+    /// Set the heading shown for an action set.
+    pub fn with_set_label<S, L>(mut self, set: S, label: L) -> Self
+    where
+        S: Into<String>,
+        L: Into<String>,
+    {
+        self.sets.insert(set.into(), label.into());
+        self
+    }
+
+    /// Only show bindings for the given kind of device. Actions without any
+    /// bindings for the device are left out.
+    ///
+    /// A binding that mixes devices, like a gamepad button held with a mouse
+    /// button, is shown for each device it uses.
+    pub fn with_device(self, device: DeviceClass) -> Self {
+        Self {
+            device: Some(device),
+            ..self
+        }
+    }
+
+    /// Name inputs using a table of display names, like a translation.
+    pub fn with_display_names(self, names: &'a DisplayNames) -> Self {
+        Self { names, ..self }
+    }
+
+    /// Name gamepad inputs the way they're printed on a family of
+    /// controllers, like "Cross" instead of "A" for PlayStation controllers.
+    pub fn with_controller_family(self, family: ControllerFamily) -> Self {
+        Self { family, ..self }
+    }
+
+    /// Render the reference in the given format.
+    pub fn render(&self, format: ReferenceFormat) -> String {
+        let groups = self.groups();
+
+        match format {
+            ReferenceFormat::Markdown => render_markdown(&groups),
+            ReferenceFormat::Html => render_html(&groups),
+            ReferenceFormat::Text => render_text(&groups),
+        }
+    }
+
+    fn groups(&self) -> Vec<Group> {
+        let mut groups = Vec::new();
+
+        for (set_name, set) in &self.bindings.action_sets {
+            let mut rows = Vec::new();
+
+            for (action_name, list) in &set.actions {
+                let full_name = format!("{}/{}", set_name, action_name);
+                let display = self.actions.get(&full_name);
+
+                if display.is_some_and(|display| display.hidden) {
+                    continue;
+                }
+
+                let labels = Labels {
+                    names: self.names,
+                    family: self.family,
+                    qualify_gamepad: self.device.is_none(),
+                };
+
+                let controls: Vec<_> = list
+                    .iter()
+                    .filter(|binding| {
+                        self.device.is_none_or(|device| {
+                            binding
+                                .inputs()
+                                .iter()
+                                .any(|input| input.device_class() == device)
+                        })
+                    })
+                    .map(|binding| binding_label(binding, &labels))
+                    .collect();
+
+                if controls.is_empty() {
+                    continue;
+                }
+
+                rows.push(Row {
+                    action: display.map_or_else(|| action_name.clone(), |d| d.label.clone()),
+                    controls,
+                    description: display.and_then(|display| display.description.clone()),
+                });
+            }
+
+            if !rows.is_empty() {
+                groups.push(Group {
+                    title: self.sets.get(set_name).unwrap_or(set_name).clone(),
+                    rows,
+                });
+            }
+        }
+
+        groups
+    }
+}
+
+/// Looks up the names of inputs for a [`ControlsReference`].
+struct Labels<'a> {
+    names: &'a DisplayNames,
+    family: ControllerFamily,
+    qualify_gamepad: bool,
+}
+
+impl Labels<'_> {
+    fn long<I: Into<Input>>(&self, input: I) -> Cow<'_, str> {
+        let input = input.into();
+        let name = self.names.family_long(self.family, input);
+
+        match input {
+            Input::Gamepad(_) if self.qualify_gamepad => format!("Gamepad {}", name).into(),
+            _ => name.into(),
+        }
+    }
+}
+
+/// Returns a friendly label for a binding, like "Left Ctrl + S".
+fn binding_label(binding: &Binding, names: &Labels) -> String {
+    match binding {
+        Binding::Button(button) => names.long(*button).into_owned(),
+        Binding::Chord(chord) => format!(
+            "{} + {}",
+            names.long(chord.modifier),
+            names.long(chord.button)
+        ),
+        Binding::Threshold(threshold) => match threshold.source {
+            ThresholdSource::Axis1d(axis, AxisDirection::Positive) => names.long(axis).into_owned(),
+            ThresholdSource::Axis1d(axis, AxisDirection::Negative) => {
+                format!("{} (-)", names.long(axis))
+            }
+            ThresholdSource::Axis2d(axis, direction) => {
+                let direction = match direction {
+                    Axis2dDirection::PositiveX => "+X",
+                    Axis2dDirection::NegativeX => "-X",
+                    Axis2dDirection::PositiveY => "+Y",
+                    Axis2dDirection::NegativeY => "-Y",
+                };
+
+                format!("{} ({})", names.long(axis), direction)
+            }
+        },
+        Binding::Axis1d(axis) => axis1d_label(axis, names),
+        Binding::Axis2d(Axis2dBinding::Axis { axis, .. }) => names.long(*axis).into_owned(),
+        Binding::Axis2d(Axis2dBinding::Individual { x, y }) => match (x, y) {
+            // Written like "W / A / S / D".
+            (
+                Axis1dBinding::Buttons {
+                    neg: left,
+                    pos: right,
+                    ..
+                },
+                Axis1dBinding::Buttons {
+                    neg: down, pos: up, ..
+                },
+            ) => [up, left, down, right]
+                .map(|button| names.long(*button))
+                .join(" / "),
+            _ => format!("{} / {}", axis1d_label(x, names), axis1d_label(y, names)),
+        },
+        Binding::Axis3d(Axis3dBinding::Individual { x, y, z }) => format!(
+            "{} / {} / {}",
+            axis1d_label(x, names),
+            axis1d_label(y, names),
+            axis1d_label(z, names)
+        ),
+    }
+}
+
+fn axis1d_label(axis: &Axis1dBinding, names: &Labels) -> String {
+    match axis {
+        Axis1dBinding::Buttons { neg, pos, .. } => {
+            format!("{} / {}", names.long(*neg), names.long(*pos))
+        }
+        Axis1dBinding::Axis { axis, .. } => names.long(*axis).into_owned(),
+    }
+}
+
+fn has_descriptions(group: &Group) -> bool {
+    group.rows.iter().any(|row| row.description.is_some())
+}
+
+fn render_markdown(groups: &[Group]) -> String {
+    let mut out = String::new();
+
+    for (i, group) in groups.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+
+        writeln!(out, "## {}\n", group.title).unwrap();
+
+        if has_descriptions(group) {
+            out.push_str("| Action | Controls | Description |\n| --- | --- | --- |\n");
+        } else {
+            out.push_str("| Action | Controls |\n| --- | --- |\n");
+        }
+
+        for row in &group.rows {
+            write!(
+                out,
+                "| {} | {} |",
+                escape_markdown(&row.action),
+                escape_markdown(&row.controls.join(", "))
+            )
+            .unwrap();
+
+            if has_descriptions(group) {
+                let description = row.description.as_deref().unwrap_or("");
+                write!(out, " {} |", escape_markdown(description)).unwrap();
+            }
+
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+fn render_html(groups: &[Group]) -> String {
+    let mut out = String::new();
+
+    for group in groups {
+        writeln!(
+            out,
+            "<section>\n<h2>{}</h2>\n<table>",
+            escape_html(&group.title)
+        )
+        .unwrap();
+
+        if has_descriptions(group) {
+            out.push_str("<tr><th>Action</th><th>Controls</th><th>Description</th></tr>\n");
+        } else {
+            out.push_str("<tr><th>Action</th><th>Controls</th></tr>\n");
+        }
+
+        for row in &group.rows {
+            let controls: Vec<_> = row
+                .controls
+                .iter()
+                .map(|control| format!("<kbd>{}</kbd>", escape_html(control)))
+                .collect();
+
+            write!(
+                out,
+                "<tr><td>{}</td><td>{}</td>",
+                escape_html(&row.action),
+                controls.join(", ")
+            )
+            .unwrap();
+
+            if has_descriptions(group) {
+                let description = row.description.as_deref().unwrap_or("");
+                write!(out, "<td>{}</td>", escape_html(description)).unwrap();
+            }
+
+            out.push_str("</tr>\n");
+        }
+
+        out.push_str("</table>\n</section>\n");
+    }
+
+    out
+}
+
+fn render_text(groups: &[Group]) -> String {
+    let mut out = String::new();
+
+    for (i, group) in groups.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+
+        let width = group
+            .rows
+            .iter()
+            .map(|row| row.action.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        writeln!(out, "{}", group.title).unwrap();
+        writeln!(out, "{}", "-".repeat(group.title.chars().count())).unwrap();
+
+        for row in &group.rows {
+            let line = format!(
+                "{:width$}  {}",
+                row.action,
+                row.controls.join(", "),
+                width = width
+            );
+
+            match &row.description {
+                Some(description) => writeln!(out, "{}  ({})", line, description).unwrap(),
+                None => writeln!(out, "{}", line).unwrap(),
+            }
+        }
+    }
+
+    out
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ActionSetBindings, ChordBinding, DisplayName, GamepadButton, KeyboardKey, MouseButton,
+    };
+
+    use super::*;
+
+    fn bindings() -> Bindings {
+        let mut gameplay = ActionSetBindings::new();
+        gameplay.insert(
+            "jump",
+            vec![KeyboardKey::Space.into(), GamepadButton::A.into()],
+        );
+        gameplay.insert(
+            "move",
+            vec![Binding::Axis2d(Axis2dBinding::Individual {
+                x: Axis1dBinding::Buttons {
+                    neg: KeyboardKey::A.into(),
+                    pos: KeyboardKey::D.into(),
+                    sensitivity: 1.0,
+                    ramp: None,
+                },
+                y: Axis1dBinding::Buttons {
+                    neg: KeyboardKey::S.into(),
+                    pos: KeyboardKey::W.into(),
+                    sensitivity: 1.0,
+                    ramp: None,
+                },
+            })],
+        );
+        gameplay.insert("noclip", vec![KeyboardKey::F1.into()]);
+
+        let mut editor = ActionSetBindings::new();
+        editor.insert(
+            "save",
+            vec![ChordBinding {
+                modifier: KeyboardKey::LControl.into(),
+                button: KeyboardKey::S.into(),
+            }
+            .into()],
+        );
+
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", gameplay);
+        bindings.insert("editor", editor);
+        bindings
+    }
+
+    #[test]
+    fn markdown_reference() {
+        let bindings = bindings();
+        let reference = ControlsReference::new(&bindings)
+            .with_set_label("gameplay", "Gameplay")
+            .with_set_label("editor", "Editor")
+            .with_action(
+                "gameplay/jump",
+                ActionDisplay::new("Jump").with_description("Leap | hop"),
+            )
+            .with_action("gameplay/noclip", ActionDisplay::new("Noclip").hidden());
+
+        assert_eq!(
+            reference.render(ReferenceFormat::Markdown),
+            concat!(
+                "## Editor\n\n",
+                "| Action | Controls |\n",
+                "| --- | --- |\n",
+                "| save | Left Ctrl + S |\n",
+                "\n",
+                "## Gameplay\n\n",
+                "| Action | Controls | Description |\n",
+                "| --- | --- | --- |\n",
+                "| Jump | Space, Gamepad A | Leap \\| hop |\n",
+                "| move | W / A / S / D |  |\n",
+            )
+        );
+    }
+
+    #[test]
+    fn device_filter() {
+        let bindings = bindings();
+        let reference = ControlsReference::new(&bindings)
+            .with_action("gameplay/jump", ActionDisplay::new("Jump <high>"))
+            .with_device(DeviceClass::Gamepad);
+
+        assert_eq!(
+            reference.render(ReferenceFormat::Html),
+            concat!(
+                "<section>\n<h2>gameplay</h2>\n<table>\n",
+                "<tr><th>Action</th><th>Controls</th></tr>\n",
+                "<tr><td>Jump &lt;high&gt;</td><td><kbd>A</kbd></td></tr>\n",
+                "</table>\n</section>\n",
+            )
+        );
+
+        assert_eq!(
+            reference.render(ReferenceFormat::Text),
+            "gameplay\n--------\nJump <high>  A\n"
+        );
+    }
+
+    #[test]
+    fn display_names() {
+        let bindings = bindings();
+        let mut names = DisplayNames::new();
+        names.insert(KeyboardKey::Space, DisplayName::new("Esp", "Espace"));
+        names.insert(
+            KeyboardKey::LControl,
+            DisplayName::new("Ctrl G", "Ctrl gauche"),
+        );
+
+        let reference = ControlsReference::new(&bindings)
+            .with_display_names(&names)
+            .with_device(DeviceClass::KeyboardMouse);
+
+        assert_eq!(
+            reference.render(ReferenceFormat::Text),
+            concat!(
+                "editor\n------\nsave  Ctrl gauche + S\n",
+                "\n",
+                "gameplay\n--------\njump    Espace\nmove    W / A / S / D\nnoclip  F1\n",
+            )
+        );
+    }
+
+    #[test]
+    fn mixed_devices_and_families() {
+        let mut gameplay = ActionSetBindings::new();
+        gameplay.insert(
+            "aim",
+            vec![ChordBinding {
+                modifier: GamepadButton::LeftTrigger.into(),
+                button: MouseButton::Button2.into(),
+            }
+            .into()],
+        );
+        gameplay.insert("jump", vec![GamepadButton::A.into()]);
+
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", gameplay);
+
+        let reference = ControlsReference::new(&bindings);
+
+        assert_eq!(
+            reference
+                .clone()
+                .with_device(DeviceClass::KeyboardMouse)
+                .render(ReferenceFormat::Text),
+            "gameplay\n--------\naim  Left Trigger + Right Mouse Button\n"
+        );
+        assert_eq!(
+            reference
+                .with_device(DeviceClass::Gamepad)
+                .with_controller_family(ControllerFamily::PlayStation)
+                .render(ReferenceFormat::Text),
+            "gameplay\n--------\naim   L2 Trigger + Right Mouse Button\njump  Cross\n"
+        );
+    }
+}
@@ -0,0 +1,504 @@
+//! The serialized form of [`Binding`].
+//!
+//! Simple bindings are written as strings, like `"keyboard/space"` or
+//! `"gamepad/leftstick"`. A pair of buttons like `["keyboard/a", "keyboard/d"]`
+//! makes a 1D axis. Everything else is written as a map:
+//!
+//! - `{ axis, sensitivity }` for 1D or 2D axes
+//! - `{ neg, pos, sensitivity, ramp }` for 1D axes made of buttons
+//! - `{ x, y }` and `{ x, y, z }` for 2D and 3D axes made of 1D axes
+//! - `{ threshold, direction, press, release }` for thresholds
+//! - `{ modifier, button }` for chords
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Axis1d, Axis2d, Button};
+
+use super::{
+    Axis1dBinding, Axis2dBinding, Axis2dDirection, Axis3dBinding, AxisDirection, Binding,
+    ChordBinding, Ramp, ThresholdBinding, ThresholdSource,
+};
+
+/// A binding with its inputs still written as strings, so that they can be
+/// renamed before being parsed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(super) enum BindingRepr {
+    Name(String),
+    Pair(String, String),
+    Map(Box<BindingMap>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct BindingMap {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    axis: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sensitivity: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    neg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pos: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ramp: Option<Ramp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    x: Option<BindingRepr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    y: Option<BindingRepr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    z: Option<BindingRepr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    threshold: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    direction: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    press: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    release: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modifier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    button: Option<String>,
+}
+
+impl Serialize for Binding {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        BindingRepr::from_binding(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BindingRepr::deserialize(deserializer)?
+            .into_binding()
+            .map_err(|err| D::Error::custom(format!("{:#}", err)))
+    }
+}
+
+impl BindingRepr {
+    pub fn from_binding(binding: &Binding) -> Self {
+        match binding {
+            Binding::Button(button) => Self::Name(button.to_string()),
+            Binding::Threshold(threshold) => {
+                let (source, direction) = match threshold.source {
+                    ThresholdSource::Axis1d(axis, direction) => {
+                        (axis.to_string(), axis_direction_name(direction))
+                    }
+                    ThresholdSource::Axis2d(axis, direction) => {
+                        (axis.to_string(), axis2d_direction_name(direction))
+                    }
+                };
+
+                Self::Map(Box::new(BindingMap {
+                    threshold: Some(source),
+                    direction: Some(direction.to_owned()),
+                    press: Some(threshold.press),
+                    release: Some(threshold.release),
+                    ..Default::default()
+                }))
+            }
+            Binding::Chord(chord) => Self::Map(Box::new(BindingMap {
+                modifier: Some(chord.modifier.to_string()),
+                button: Some(chord.button.to_string()),
+                ..Default::default()
+            })),
+            Binding::Axis1d(axis) => Self::from_axis1d(axis),
+            Binding::Axis2d(Axis2dBinding::Individual { x, y }) => {
+                Self::Map(Box::new(BindingMap {
+                    x: Some(Self::from_axis1d(x)),
+                    y: Some(Self::from_axis1d(y)),
+                    ..Default::default()
+                }))
+            }
+            Binding::Axis2d(Axis2dBinding::Axis { axis, sensitivity }) => {
+                Self::from_axis(axis.to_string(), *sensitivity)
+            }
+            Binding::Axis3d(Axis3dBinding::Individual { x, y, z }) => {
+                Self::Map(Box::new(BindingMap {
+                    x: Some(Self::from_axis1d(x)),
+                    y: Some(Self::from_axis1d(y)),
+                    z: Some(Self::from_axis1d(z)),
+                    ..Default::default()
+                }))
+            }
+        }
+    }
+
+    fn from_axis1d(axis: &Axis1dBinding) -> Self {
+        match *axis {
+            Axis1dBinding::Buttons {
+                neg,
+                pos,
+                sensitivity,
+                ramp,
+            } => {
+                if sensitivity == 1.0 && ramp.is_none() {
+                    Self::Pair(neg.to_string(), pos.to_string())
+                } else {
+                    Self::Map(Box::new(BindingMap {
+                        neg: Some(neg.to_string()),
+                        pos: Some(pos.to_string()),
+                        sensitivity: sensitivity_field(sensitivity),
+                        ramp,
+                        ..Default::default()
+                    }))
+                }
+            }
+            Axis1dBinding::Axis { axis, sensitivity } => {
+                Self::from_axis(axis.to_string(), sensitivity)
+            }
+        }
+    }
+
+    /// Writes an axis as a plain string when that reads back as the same axis.
+    /// Names like `gamepad/lefttrigger` are also buttons, so they're always
+    /// written as a map.
+    fn from_axis(name: String, sensitivity: f32) -> Self {
+        if sensitivity == 1.0 && name.parse::<Button>().is_err() {
+            let is_axis1d = name.parse::<Axis1d>().is_ok();
+            let is_axis2d = name.parse::<Axis2d>().is_ok();
+
+            if is_axis1d != is_axis2d {
+                return Self::Name(name);
+            }
+        }
+
+        Self::Map(Box::new(BindingMap {
+            axis: Some(name),
+            sensitivity: sensitivity_field(sensitivity),
+            ..Default::default()
+        }))
+    }
+
+    pub fn into_binding(self) -> anyhow::Result<Binding> {
+        match self {
+            Self::Name(name) => {
+                if let Ok(button) = name.parse::<Button>() {
+                    Ok(Binding::Button(button))
+                } else {
+                    parse_axis(&name, 1.0)
+                }
+            }
+            Self::Pair(neg, pos) => Ok(Binding::Axis1d(Axis1dBinding::Buttons {
+                neg: neg.parse()?,
+                pos: pos.parse()?,
+                sensitivity: 1.0,
+                ramp: None,
+            })),
+            Self::Map(map) => map.into_binding(),
+        }
+    }
+
+    /// Returns every input name in this binding.
+    pub fn inputs_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            Self::Name(name) => vec![name],
+            Self::Pair(neg, pos) => vec![neg, pos],
+            Self::Map(map) => {
+                let BindingMap {
+                    axis,
+                    neg,
+                    pos,
+                    x,
+                    y,
+                    z,
+                    threshold,
+                    modifier,
+                    button,
+                    ..
+                } = &mut **map;
+
+                let mut inputs: Vec<_> = [axis, neg, pos, threshold, modifier, button]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+
+                for nested in [x, y, z].into_iter().flatten() {
+                    inputs.extend(nested.inputs_mut());
+                }
+
+                inputs
+            }
+        }
+    }
+
+    fn into_axis1d(self) -> anyhow::Result<Axis1dBinding> {
+        match self.into_binding()? {
+            Binding::Axis1d(axis) => Ok(axis),
+            // Bare buttons aren't axes, but a name like `gamepad/lefttrigger`
+            // is both, so read it as the axis here.
+            Binding::Button(button) => match button.to_string().parse() {
+                Ok(axis) => Ok(Axis1dBinding::Axis {
+                    axis,
+                    sensitivity: 1.0,
+                }),
+                Err(_) => anyhow::bail!("expected a 1D axis, found button '{}'", button),
+            },
+            other => anyhow::bail!("expected a 1D axis, found a {:?} binding", other.shape()),
+        }
+    }
+}
+
+impl BindingMap {
+    fn into_binding(self) -> anyhow::Result<Binding> {
+        let sensitivity = self.sensitivity.unwrap_or(1.0);
+
+        if let (Some(modifier), Some(button)) = (self.modifier, self.button) {
+            return Ok(Binding::Chord(ChordBinding {
+                modifier: modifier.parse()?,
+                button: button.parse()?,
+            }));
+        }
+
+        if let Some(threshold) = self.threshold {
+            let direction =
+                anyhow::Context::context(self.direction, "threshold bindings need a 'direction'")?;
+            let press = anyhow::Context::context(self.press, "threshold bindings need 'press'")?;
+            let release = self.release.unwrap_or(press);
+            anyhow::ensure!(
+                release <= press,
+                "threshold 'release' ({}) must be at most 'press' ({})",
+                release,
+                press
+            );
+
+            let source = if let Ok(axis) = threshold.parse::<Axis1d>() {
+                ThresholdSource::Axis1d(axis, parse_axis_direction(&direction)?)
+            } else {
+                let axis = threshold.parse::<Axis2d>()?;
+                ThresholdSource::Axis2d(axis, parse_axis2d_direction(&direction)?)
+            };
+
+            return Ok(Binding::Threshold(ThresholdBinding {
+                source,
+                press,
+                release,
+            }));
+        }
+
+        match (self.x, self.y, self.z) {
+            (Some(x), Some(y), Some(z)) => {
+                return Ok(Binding::Axis3d(Axis3dBinding::Individual {
+                    x: x.into_axis1d()?,
+                    y: y.into_axis1d()?,
+                    z: z.into_axis1d()?,
+                }));
+            }
+            (Some(x), Some(y), None) => {
+                return Ok(Binding::Axis2d(Axis2dBinding::Individual {
+                    x: x.into_axis1d()?,
+                    y: y.into_axis1d()?,
+                }));
+            }
+            (None, None, None) => {}
+            _ => anyhow::bail!("axis bindings need 'x' and 'y', and optionally 'z'"),
+        }
+
+        if let (Some(neg), Some(pos)) = (self.neg, self.pos) {
+            return Ok(Binding::Axis1d(Axis1dBinding::Buttons {
+                neg: neg.parse()?,
+                pos: pos.parse()?,
+                sensitivity,
+                ramp: self.ramp,
+            }));
+        }
+
+        if let Some(axis) = self.axis {
+            return parse_axis(&axis, sensitivity);
+        }
+
+        anyhow::bail!("could not tell what kind of binding this is")
+    }
+}
+
+fn parse_axis(name: &str, sensitivity: f32) -> anyhow::Result<Binding> {
+    if let Ok(axis) = name.parse::<Axis1d>() {
+        Ok(Binding::Axis1d(Axis1dBinding::Axis { axis, sensitivity }))
+    } else if let Ok(axis) = name.parse::<Axis2d>() {
+        Ok(Binding::Axis2d(Axis2dBinding::Axis { axis, sensitivity }))
+    } else {
+        anyhow::bail!("unknown input '{}'", name)
+    }
+}
+
+fn sensitivity_field(sensitivity: f32) -> Option<f32> {
+    if sensitivity == 1.0 {
+        None
+    } else {
+        Some(sensitivity)
+    }
+}
+
+fn axis_direction_name(direction: AxisDirection) -> &'static str {
+    match direction {
+        AxisDirection::Positive => "positive",
+        AxisDirection::Negative => "negative",
+    }
+}
+
+fn axis2d_direction_name(direction: Axis2dDirection) -> &'static str {
+    match direction {
+        Axis2dDirection::PositiveX => "positive_x",
+        Axis2dDirection::NegativeX => "negative_x",
+        Axis2dDirection::PositiveY => "positive_y",
+        Axis2dDirection::NegativeY => "negative_y",
+    }
+}
+
+fn parse_axis_direction(name: &str) -> anyhow::Result<AxisDirection> {
+    match name {
+        "positive" => Ok(AxisDirection::Positive),
+        "negative" => Ok(AxisDirection::Negative),
+        _ => anyhow::bail!(
+            "unknown direction '{}'; expected 'positive' or 'negative'",
+            name
+        ),
+    }
+}
+
+fn parse_axis2d_direction(name: &str) -> anyhow::Result<Axis2dDirection> {
+    match name {
+        "positive_x" => Ok(Axis2dDirection::PositiveX),
+        "negative_x" => Ok(Axis2dDirection::NegativeX),
+        "positive_y" => Ok(Axis2dDirection::PositiveY),
+        "negative_y" => Ok(Axis2dDirection::NegativeY),
+        _ => anyhow::bail!(
+            "unknown direction '{}'; expected 'positive_x', 'negative_x', 'positive_y' or 'negative_y'",
+            name
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{GamepadAxis1d, GamepadAxis2d, GamepadButton, KeyboardKey, MouseAxis2d};
+
+    use super::*;
+
+    fn round_trip(binding: Binding, json: &str) {
+        assert_eq!(serde_json::to_string(&binding).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Binding>(json).unwrap(), binding);
+    }
+
+    #[test]
+    fn simple_bindings() {
+        round_trip(KeyboardKey::Space.into(), r#""keyboard/space""#);
+        round_trip(
+            Axis2dBinding::Axis {
+                axis: Axis2d::Mouse(MouseAxis2d::XY),
+                sensitivity: 1.0,
+            }
+            .into(),
+            r#""mouse/xy""#,
+        );
+        round_trip(
+            Axis1dBinding::Buttons {
+                neg: KeyboardKey::A.into(),
+                pos: KeyboardKey::D.into(),
+                sensitivity: 1.0,
+                ramp: None,
+            }
+            .into(),
+            r#"["keyboard/a","keyboard/d"]"#,
+        );
+    }
+
+    #[test]
+    fn map_bindings() {
+        round_trip(
+            Axis1dBinding::Axis {
+                axis: Axis1d::Gamepad(GamepadAxis1d::LeftTrigger),
+                sensitivity: 1.0,
+            }
+            .into(),
+            r#"{"axis":"gamepad/lefttrigger"}"#,
+        );
+        round_trip(
+            Axis1dBinding::Buttons {
+                neg: KeyboardKey::Q.into(),
+                pos: KeyboardKey::E.into(),
+                sensitivity: 2.0,
+                ramp: Some(Ramp {
+                    rise: 4.0,
+                    fall: 8.0,
+                    snap: true,
+                }),
+            }
+            .into(),
+            r#"{"sensitivity":2.0,"neg":"keyboard/q","pos":"keyboard/e","ramp":{"rise":4.0,"fall":8.0,"snap":true}}"#,
+        );
+        round_trip(
+            ThresholdBinding {
+                source: ThresholdSource::Axis2d(
+                    Axis2d::Gamepad(GamepadAxis2d::LeftStick),
+                    Axis2dDirection::NegativeY,
+                ),
+                press: 0.5,
+                release: 0.25,
+            }
+            .into(),
+            r#"{"threshold":"gamepad/leftstick","direction":"negative_y","press":0.5,"release":0.25}"#,
+        );
+        round_trip(
+            ChordBinding {
+                modifier: GamepadButton::LeftShoulder.into(),
+                button: GamepadButton::A.into(),
+            }
+            .into(),
+            r#"{"modifier":"gamepad/leftshoulder","button":"gamepad/a"}"#,
+        );
+    }
+
+    #[test]
+    fn nested_axes() {
+        let json = r#"{
+            "x": "gamepad/leftstickx",
+            "y": ["gamepad/lefttrigger", "gamepad/righttrigger"],
+            "z": { "axis": "gamepad/leftsticky", "sensitivity": -1.0 }
+        }"#;
+
+        let binding: Binding = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            binding,
+            Binding::Axis3d(Axis3dBinding::Individual {
+                x: Axis1dBinding::Axis {
+                    axis: Axis1d::Gamepad(GamepadAxis1d::LeftStickX),
+                    sensitivity: 1.0,
+                },
+                y: Axis1dBinding::Buttons {
+                    neg: GamepadButton::LeftTrigger.into(),
+                    pos: GamepadButton::RightTrigger.into(),
+                    sensitivity: 1.0,
+                    ramp: None,
+                },
+                z: Axis1dBinding::Axis {
+                    axis: Axis1d::Gamepad(GamepadAxis1d::LeftStickY),
+                    sensitivity: -1.0,
+                },
+            })
+        );
+
+        let error = serde_json::from_str::<Binding>(r#"{"x": "keyboard/a"}"#).unwrap_err();
+        assert!(error.to_string().contains("'x' and 'y'"), "{}", error);
+    }
+
+    #[test]
+    fn inverted_threshold() {
+        let json = r#"{"threshold":"gamepad/lefttrigger","direction":"positive","press":0.25,"release":0.5}"#;
+
+        let error = serde_json::from_str::<Binding>(json).unwrap_err();
+        assert!(
+            error.to_string().contains("must be at most 'press'"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! JSON Schema for the serialized form of [`Bindings`], so that editors can
+//! complete and check input names in bindings files.
+
+use serde_json::{json, Map, Value};
+
+use crate::session::Session;
+use crate::{Axis1d, Axis2d, Button};
+
+use super::{BindingShape, Bindings};
+
+const SCHEMA: &str = "http://json-schema.org/draft-07/schema#";
+
+impl Bindings {
+    /// Generate a JSON Schema that any set of bindings matches. Every input
+    /// name is listed, but action sets and actions can have any name.
+    pub fn json_schema() -> Value {
+        schema(json!({
+            "type": "object",
+            "additionalProperties": {
+                "type": "object",
+                "additionalProperties": binding_list(&BindingShape::ALL),
+            },
+        }))
+    }
+}
+
+impl Session {
+    /// Generate a JSON Schema for bindings used with this session. Only the
+    /// action sets and actions created from this session are allowed, and
+    /// each action only allows the kinds of bindings that it can read.
+    pub fn bindings_json_schema(&self) -> Value {
+        let mut sets = Map::new();
+
+        for set in self.action_sets() {
+            sets.insert(
+                set.name,
+                json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false,
+                }),
+            );
+        }
+
+        for action in self.actions() {
+            let Some((_, action_name)) = action.name.split_once('/') else {
+                continue;
+            };
+
+            let set = sets.entry(action.action_set).or_insert_with(|| {
+                json!({
+                    "type": "object",
+                    "properties": {},
+                    "additionalProperties": false,
+                })
+            });
+
+            set["properties"][action_name] = binding_list(&action.accepts);
+        }
+
+        schema(json!({
+            "type": "object",
+            "properties": sets,
+            "additionalProperties": false,
+        }))
+    }
+}
+
+/// Add the definitions shared by every schema.
+fn schema(root: Value) -> Value {
+    let mut schema = json!({
+        "$schema": SCHEMA,
+        "title": "Keybee bindings",
+        "definitions": definitions(),
+    });
+
+    if let (Value::Object(schema), Value::Object(root)) = (&mut schema, root) {
+        schema.extend(root);
+    }
+
+    schema
+}
+
+fn binding_list(shapes: &[BindingShape]) -> Value {
+    let refs: Vec<_> = shapes.iter().map(|&shape| shape_ref(shape)).collect();
+
+    json!({
+        "type": "array",
+        "items": { "anyOf": refs },
+    })
+}
+
+fn shape_ref(shape: BindingShape) -> Value {
+    let name = match shape {
+        BindingShape::Button => "buttonBinding",
+        BindingShape::Threshold => "thresholdBinding",
+        BindingShape::Chord => "chordBinding",
+        BindingShape::Axis1d => "axis1dBinding",
+        BindingShape::Axis2d => "axis2dBinding",
+        BindingShape::Axis3d => "axis3dBinding",
+    };
+
+    json!({ "$ref": format!("#/definitions/{}", name) })
+}
+
+fn names<T: ToString>(values: impl Iterator<Item = T>) -> Value {
+    let names: Vec<_> = values.map(|value| value.to_string()).collect();
+    json!({ "type": "string", "enum": names })
+}
+
+fn is_button<T: ToString>(input: &T) -> bool {
+    input.to_string().parse::<Button>().is_ok()
+}
+
+fn definitions() -> Value {
+    json!({
+        "button": names(Button::all()),
+        "axis1d": names(Axis1d::all()),
+        "axis2d": names(Axis2d::all()),
+        // Bare strings are read as buttons first, so axes that share a name
+        // with a button, like `gamepad/lefttrigger`, need `{ "axis": ... }`.
+        "bareAxis1d": names(Axis1d::all().filter(|axis| !is_button(axis))),
+        "bareAxis2d": names(Axis2d::all().filter(|axis| !is_button(axis))),
+        "sensitivity": { "type": "number" },
+        "ramp": {
+            "type": "object",
+            "properties": {
+                "rise": { "type": "number" },
+                "fall": { "type": "number" },
+                "snap": { "type": "boolean" },
+            },
+            "required": ["rise", "fall"],
+            "additionalProperties": false,
+        },
+        "buttonBinding": { "$ref": "#/definitions/button" },
+        "thresholdBinding": {
+            "type": "object",
+            "properties": {
+                "threshold": {
+                    "anyOf": [
+                        { "$ref": "#/definitions/axis1d" },
+                        { "$ref": "#/definitions/axis2d" },
+                    ],
+                },
+                "direction": {
+                    "enum": [
+                        "positive",
+                        "negative",
+                        "positive_x",
+                        "negative_x",
+                        "positive_y",
+                        "negative_y",
+                    ],
+                },
+                "press": { "type": "number" },
+                "release": { "type": "number" },
+            },
+            "required": ["threshold", "direction", "press"],
+            "additionalProperties": false,
+        },
+        "chordBinding": {
+            "type": "object",
+            "properties": {
+                "modifier": { "$ref": "#/definitions/button" },
+                "button": { "$ref": "#/definitions/button" },
+            },
+            "required": ["modifier", "button"],
+            "additionalProperties": false,
+        },
+        "axis1dBinding": {
+            "anyOf": [
+                { "$ref": "#/definitions/bareAxis1d" },
+                {
+                    "type": "array",
+                    "items": [
+                        { "$ref": "#/definitions/button" },
+                        { "$ref": "#/definitions/button" },
+                    ],
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "axis": { "$ref": "#/definitions/axis1d" },
+                        "sensitivity": { "$ref": "#/definitions/sensitivity" },
+                    },
+                    "required": ["axis"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "neg": { "$ref": "#/definitions/button" },
+                        "pos": { "$ref": "#/definitions/button" },
+                        "sensitivity": { "$ref": "#/definitions/sensitivity" },
+                        "ramp": { "$ref": "#/definitions/ramp" },
+                    },
+                    "required": ["neg", "pos"],
+                    "additionalProperties": false,
+                },
+            ],
+        },
+        "axis2dBinding": {
+            "anyOf": [
+                { "$ref": "#/definitions/bareAxis2d" },
+                {
+                    "type": "object",
+                    "properties": {
+                        "axis": { "$ref": "#/definitions/axis2d" },
+                        "sensitivity": { "$ref": "#/definitions/sensitivity" },
+                    },
+                    "required": ["axis"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "x": { "$ref": "#/definitions/axis1dBinding" },
+                        "y": { "$ref": "#/definitions/axis1dBinding" },
+                    },
+                    "required": ["x", "y"],
+                    "additionalProperties": false,
+                },
+            ],
+        },
+        "axis3dBinding": {
+            "type": "object",
+            "properties": {
+                "x": { "$ref": "#/definitions/axis1dBinding" },
+                "y": { "$ref": "#/definitions/axis1dBinding" },
+                "z": { "$ref": "#/definitions/axis1dBinding" },
+            },
+            "required": ["x", "y", "z"],
+            "additionalProperties": false,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Axis2dAction, BoolAction};
+
+    use super::super::repr::BindingRepr;
+    use super::*;
+
+    fn enum_names(schema: &Value, definition: &str) -> Vec<String> {
+        schema["definitions"][definition]["enum"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|name| name.as_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn lists_inputs() {
+        let schema = Bindings::json_schema();
+        let buttons = schema["definitions"]["button"]["enum"].as_array().unwrap();
+
+        assert!(buttons.contains(&json!("keyboard/leftshift")));
+        assert!(buttons.contains(&json!("mouse/button1")));
+        assert!(buttons.contains(&json!("gamepad/dpadup")));
+        assert_eq!(
+            schema["definitions"]["axis2d"]["enum"],
+            json!(["mouse/xy", "gamepad/leftstick", "gamepad/rightstick"])
+        );
+    }
+
+    #[test]
+    fn lists_session_actions() {
+        let session = Session::new();
+        let gameplay = session.create_action_set("gameplay");
+        let _jump = gameplay.create_action("jump", BoolAction);
+        let _movement = gameplay.create_action("move", Axis2dAction);
+        let _menu = session.create_action_set("menu");
+
+        let schema = session.bindings_json_schema();
+        let sets = &schema["properties"];
+
+        assert_eq!(sets["menu"]["properties"], json!({}));
+        assert_eq!(
+            sets["gameplay"]["properties"]["move"]["items"]["anyOf"],
+            json!([{ "$ref": "#/definitions/axis2dBinding" }])
+        );
+        assert!(sets["gameplay"]["properties"]["jump"].is_object());
+        assert_eq!(schema["additionalProperties"], json!(false));
+    }
+
+    #[test]
+    fn bare_names_load_as_listed() {
+        let schema = Bindings::json_schema();
+
+        for (definition, shape) in [
+            ("button", BindingShape::Button),
+            ("bareAxis1d", BindingShape::Axis1d),
+            ("bareAxis2d", BindingShape::Axis2d),
+        ] {
+            for name in enum_names(&schema, definition) {
+                let binding = BindingRepr::Name(name.clone()).into_binding().unwrap();
+                assert_eq!(binding.shape(), shape, "{} in {}", name, definition);
+            }
+        }
+
+        let bare_axes = enum_names(&schema, "bareAxis1d");
+        assert!(!bare_axes.contains(&"gamepad/lefttrigger".to_owned()));
+        assert!(bare_axes.contains(&"gamepad/leftstickx".to_owned()));
+    }
+}
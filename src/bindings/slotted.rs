@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::DeviceClass;
+
+use super::{ActionSetBindings, Binding, Bindings};
+
+/// Describes the binding slots that each action has, like the "Primary",
+/// "Secondary" and "Gamepad" columns of a settings screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotLayout {
+    slots: Vec<Slot>,
+}
+
+/// A single slot in a [`SlotLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slot {
+    pub name: String,
+    pub device: DeviceClass,
+}
+
+impl SlotLayout {
+    /// Create a layout with no slots.
+    pub fn new() -> Self {
+        Self { slots: Vec::new() }
+    }
+
+    /// Add a slot for the given kind of device after every existing slot.
+    pub fn with_slot<S: Into<String>>(mut self, name: S, device: DeviceClass) -> Self {
+        self.slots.push(Slot {
+            name: name.into(),
+            device,
+        });
+        self
+    }
+
+    /// Returns every slot, in order.
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    /// Returns the index of the slot with the given name.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.slots.iter().position(|slot| slot.name == name)
+    }
+
+    /// Returns the indices of every slot for the given kind of device.
+    pub fn slots_for(&self, device: DeviceClass) -> impl Iterator<Item = usize> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(move |(_, slot)| slot.device == device)
+            .map(|(index, _)| index)
+    }
+}
+
+impl Default for SlotLayout {
+    /// Two keyboard and mouse slots named `primary` and `secondary`, and one
+    /// `gamepad` slot.
+    fn default() -> Self {
+        Self::new()
+            .with_slot("primary", DeviceClass::KeyboardMouse)
+            .with_slot("secondary", DeviceClass::KeyboardMouse)
+            .with_slot("gamepad", DeviceClass::Gamepad)
+    }
+}
+
+/// Bindings where each action has a fixed list of slots described by a
+/// [`SlotLayout`], any of which can be empty.
+///
+/// Each action is serialized as a list with one entry per slot, using `null`
+/// for empty slots so that every binding stays in its column:
+///
+/// ```json
+/// { "gameplay": { "jump": ["keyboard/space", null, "gamepad/a"] } }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SlottedBindings {
+    pub action_sets: BTreeMap<String, BTreeMap<String, Vec<Option<Binding>>>>,
+}
+
+impl SlottedBindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort plain bindings into slots. Each binding goes into the first empty
+    /// slot for its kind of device.
+    ///
+    /// Bindings that don't fit are returned along with their action's full
+    /// name, like when an action has more gamepad bindings than gamepad slots,
+    /// or a binding mixes inputs from different kinds of devices.
+    pub fn from_bindings(
+        bindings: &Bindings,
+        layout: &SlotLayout,
+    ) -> (Self, Vec<(String, Binding)>) {
+        let mut slotted = Self::new();
+        let mut leftover = Vec::new();
+
+        for (set_name, set) in &bindings.action_sets {
+            for (action_name, list) in &set.actions {
+                let mut slots = vec![None; layout.slots().len()];
+
+                for binding in list {
+                    let free = binding.device_class().and_then(|device| {
+                        layout
+                            .slots_for(device)
+                            .find(|&index| slots[index].is_none())
+                    });
+
+                    match free {
+                        Some(index) => slots[index] = Some(*binding),
+                        None => leftover.push((format!("{}/{}", set_name, action_name), *binding)),
+                    }
+                }
+
+                slotted
+                    .action_sets
+                    .entry(set_name.clone())
+                    .or_default()
+                    .insert(action_name.clone(), slots);
+            }
+        }
+
+        (slotted, leftover)
+    }
+
+    /// Returns every slot of an action, or an empty list if the action has no
+    /// slots.
+    pub fn slots(&self, set: &str, action: &str) -> &[Option<Binding>] {
+        self.action_sets
+            .get(set)
+            .and_then(|actions| actions.get(action))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the binding in one slot of an action.
+    pub fn get(&self, set: &str, action: &str, slot: usize) -> Option<&Binding> {
+        self.slots(set, action).get(slot)?.as_ref()
+    }
+
+    /// Replace the binding in one slot of an action, returning the binding
+    /// that was there before. Passing `None` empties the slot.
+    ///
+    /// Fails if the slot isn't part of the layout or the binding is for a
+    /// different kind of device than the slot.
+    pub fn replace(
+        &mut self,
+        layout: &SlotLayout,
+        set: &str,
+        action: &str,
+        slot: usize,
+        binding: Option<Binding>,
+    ) -> anyhow::Result<Option<Binding>> {
+        let info = match layout.slots().get(slot) {
+            Some(info) => info,
+            None => anyhow::bail!("slot {} is not part of the layout", slot),
+        };
+
+        if let Some(binding) = &binding {
+            if binding.device_class() != Some(info.device) {
+                anyhow::bail!(
+                    "slot '{}' only holds {:?} bindings, but {:?} was given",
+                    info.name,
+                    info.device,
+                    binding
+                );
+            }
+        }
+
+        let slots = self
+            .action_sets
+            .entry(set.to_owned())
+            .or_default()
+            .entry(action.to_owned())
+            .or_default();
+
+        if slots.len() < layout.slots().len() {
+            slots.resize(layout.slots().len(), None);
+        }
+
+        Ok(std::mem::replace(&mut slots[slot], binding))
+    }
+
+    /// Flatten the slots into plain bindings in slot order, skipping empty
+    /// slots.
+    pub fn to_bindings(&self) -> Bindings {
+        let mut bindings = Bindings::new();
+
+        for (set_name, actions) in &self.action_sets {
+            let mut set = ActionSetBindings::new();
+
+            for (action_name, slots) in actions {
+                set.insert(
+                    action_name.clone(),
+                    slots.iter().flatten().copied().collect(),
+                );
+            }
+
+            bindings.insert(set_name.clone(), set);
+        }
+
+        bindings
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{GamepadButton, KeyboardKey, MouseButton};
+
+    use super::*;
+
+    #[test]
+    fn sort_into_slots() {
+        let mut gameplay = ActionSetBindings::new();
+        gameplay.insert(
+            "jump",
+            vec![
+                GamepadButton::A.into(),
+                KeyboardKey::Space.into(),
+                GamepadButton::B.into(),
+            ],
+        );
+        gameplay.insert(
+            "shoot",
+            vec![MouseButton::Button1.into(), KeyboardKey::F.into()],
+        );
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", gameplay);
+
+        let layout = SlotLayout::default();
+        let (slotted, leftover) = SlottedBindings::from_bindings(&bindings, &layout);
+
+        assert_eq!(
+            leftover,
+            [("gameplay/jump".to_owned(), GamepadButton::B.into())]
+        );
+
+        assert_eq!(
+            slotted.slots("gameplay", "jump"),
+            [
+                Some(KeyboardKey::Space.into()),
+                None,
+                Some(GamepadButton::A.into())
+            ]
+        );
+        assert_eq!(
+            slotted.get("gameplay", "shoot", 1),
+            Some(&KeyboardKey::F.into())
+        );
+
+        let flattened = slotted.to_bindings();
+        assert_eq!(
+            flattened.get("gameplay").unwrap().get("jump").unwrap(),
+            &[KeyboardKey::Space.into(), GamepadButton::A.into()]
+        );
+    }
+
+    #[test]
+    fn replace_and_serialize() {
+        let layout = SlotLayout::default();
+        let mut slotted = SlottedBindings::new();
+
+        let gamepad = layout.position("gamepad").unwrap();
+        let previous = slotted
+            .replace(
+                &layout,
+                "gameplay",
+                "jump",
+                gamepad,
+                Some(GamepadButton::A.into()),
+            )
+            .unwrap();
+        assert_eq!(previous, None);
+
+        assert!(slotted
+            .replace(
+                &layout,
+                "gameplay",
+                "jump",
+                0,
+                Some(GamepadButton::B.into())
+            )
+            .is_err());
+        assert!(slotted
+            .replace(&layout, "gameplay", "jump", 3, None)
+            .is_err());
+
+        let json = serde_json::to_string(&slotted).unwrap();
+        assert_eq!(json, r#"{"gameplay":{"jump":[null,null,"gamepad/a"]}}"#);
+
+        let parsed: SlottedBindings = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, slotted);
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+/// The family of a gamepad, which decides how its buttons are labeled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ControllerFamily {
+    Xbox,
+    PlayStation,
+    Nintendo,
+    SteamDeck,
+    Generic,
+}
+
+const MICROSOFT: u16 = 0x045e;
+const SONY: u16 = 0x054c;
+const NINTENDO: u16 = 0x057e;
+const VALVE: u16 = 0x28de;
+const STEAM_DECK: u16 = 0x1205;
+const STEAM_VIRTUAL_GAMEPAD: u16 = 0x11ff;
+
+impl ControllerFamily {
+    /// Guess a controller's family from its USB vendor and product IDs,
+    /// falling back to its name. Unknown controllers are `Generic`.
+    ///
+    /// Steam Input's virtual gamepad is reported as `Xbox`, since it poses as
+    /// an Xbox 360 controller no matter which controller the player is
+    /// holding. Other Valve controllers besides the Steam Deck are `Generic`.
+    pub fn detect(vendor_id: Option<u16>, product_id: Option<u16>, name: &str) -> Self {
+        match (vendor_id, product_id) {
+            (Some(MICROSOFT), _) => return Self::Xbox,
+            (Some(SONY), _) => return Self::PlayStation,
+            (Some(NINTENDO), _) => return Self::Nintendo,
+            (Some(VALVE), Some(STEAM_DECK)) => return Self::SteamDeck,
+            (Some(VALVE), Some(STEAM_VIRTUAL_GAMEPAD)) => return Self::Xbox,
+            _ => {}
+        }
+
+        let name = name.to_lowercase();
+        let name_has = |words: &[&str]| words.iter().any(|word| name.contains(word));
+
+        if name_has(&["steam deck"]) {
+            Self::SteamDeck
+        } else if name_has(&["xbox", "xinput", "steam virtual gamepad"]) {
+            Self::Xbox
+        } else if name_has(&["playstation", "dualshock", "dualsense", "ps3", "ps4", "ps5"]) {
+            Self::PlayStation
+        } else if name_has(&["nintendo", "switch", "joy-con"]) {
+            Self::Nintendo
+        } else {
+            Self::Generic
+        }
+    }
+
+    /// Detect the family of a gilrs gamepad.
+    #[cfg(feature = "gilrs")]
+    pub fn from_gilrs(gamepad: &gilrs::Gamepad<'_>) -> Self {
+        Self::detect(gamepad.vendor_id(), gamepad.product_id(), gamepad.name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn steam_virtual_gamepad() {
+        assert_eq!(
+            ControllerFamily::detect(Some(0x28de), Some(0x11ff), ""),
+            ControllerFamily::Xbox
+        );
+        assert_eq!(
+            ControllerFamily::detect(None, None, "Steam Virtual Gamepad"),
+            ControllerFamily::Xbox
+        );
+        assert_eq!(
+            ControllerFamily::detect(Some(0x28de), Some(0x1205), "Steam Virtual Gamepad"),
+            ControllerFamily::SteamDeck
+        );
+        assert_eq!(
+            ControllerFamily::detect(Some(0x28de), Some(0x1142), "Steam Controller"),
+            ControllerFamily::Generic
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::{ControllerFamily, Gamepad, GamepadAxis1d, GamepadButton};
+
+impl ControllerFamily {
+    /// Returns the name used for this family in glyph IDs, like `playstation`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Xbox => "xbox",
+            Self::PlayStation => "playstation",
+            Self::Nintendo => "nintendo",
+            Self::SteamDeck => "steamdeck",
+            Self::Generic => "generic",
+        }
+    }
+}
+
+impl GamepadButton {
+    /// Returns the full name of this button on a family of controllers.
+    /// `GamepadButton::A` is "Cross" on PlayStation controllers and "B" on
+    /// Nintendo controllers, since it's the bottom face button.
+    ///
+    /// Labels are in English. Use
+    /// [`DisplayNames::family_long`](crate::DisplayNames::family_long) to show
+    /// translated names.
+    pub fn family_label(self, family: ControllerFamily) -> &'static str {
+        family_names(self, family).map_or(self.label(), |(_, long, _)| long)
+    }
+
+    /// Returns the name printed on this button for a family of controllers,
+    /// like "LB" on Xbox controllers or "L1" on PlayStation controllers.
+    pub fn family_short_label(self, family: ControllerFamily) -> &'static str {
+        family_names(self, family).map_or(self.short_label(), |(short, _, _)| short)
+    }
+
+    /// Returns an ID for the image of this button on a family of controllers,
+    /// like `playstation/cross` or `xbox/dpadup`.
+    pub fn glyph(self, family: ControllerFamily) -> String {
+        match family_names(self, family) {
+            Some((_, _, glyph)) => format!("{}/{}", family.name(), glyph),
+            None => generic_glyph(self, family),
+        }
+    }
+}
+
+impl GamepadAxis1d {
+    /// Returns the full name of this axis on a family of controllers.
+    pub fn family_label(self, family: ControllerFamily) -> &'static str {
+        match trigger_button(self) {
+            Some(button) => button.family_label(family),
+            None => self.label(),
+        }
+    }
+
+    /// Returns the name printed on this axis for a family of controllers.
+    pub fn family_short_label(self, family: ControllerFamily) -> &'static str {
+        match trigger_button(self) {
+            Some(button) => button.family_short_label(family),
+            None => self.short_label(),
+        }
+    }
+
+    /// Returns an ID for the image of this axis on a family of controllers,
+    /// like `playstation/l2` or `xbox/leftstickx`.
+    pub fn glyph(self, family: ControllerFamily) -> String {
+        match trigger_button(self) {
+            Some(button) => button.glyph(family),
+            None => generic_glyph(self, family),
+        }
+    }
+}
+
+impl Gamepad {
+    /// Returns the full name of this input on a family of controllers.
+    pub fn family_label(self, family: ControllerFamily) -> &'static str {
+        match self {
+            Self::Button(button) => button.family_label(family),
+            Self::Axis1d(axis) => axis.family_label(family),
+            Self::Axis2d(axis) => axis.label(),
+        }
+    }
+
+    /// Returns the name printed on this input for a family of controllers.
+    pub fn family_short_label(self, family: ControllerFamily) -> &'static str {
+        match self {
+            Self::Button(button) => button.family_short_label(family),
+            Self::Axis1d(axis) => axis.family_short_label(family),
+            Self::Axis2d(axis) => axis.short_label(),
+        }
+    }
+
+    /// Returns an ID for the image of this input on a family of controllers.
+    pub fn glyph(self, family: ControllerFamily) -> String {
+        match self {
+            Self::Button(button) => button.glyph(family),
+            Self::Axis1d(axis) => axis.glyph(family),
+            Self::Axis2d(axis) => generic_glyph(axis, family),
+        }
+    }
+}
+
+/// Tracks the family of each connected controller so that button labels and
+/// glyphs can be looked up per device or per player.
+///
+/// The key is up to the game: a player index, a `gilrs::GamepadId`, or an SDL
+/// instance ID all work. Keys without a known family use
+/// [`ControllerFamily::Generic`].
+#[derive(Debug, Clone)]
+pub struct ControllerFamilies<K> {
+    families: HashMap<K, ControllerFamily>,
+}
+
+impl<K: Eq + Hash> ControllerFamilies<K> {
+    pub fn new() -> Self {
+        Self {
+            families: HashMap::new(),
+        }
+    }
+
+    /// Set the family of a controller, like when it's connected.
+    pub fn insert(&mut self, key: K, family: ControllerFamily) {
+        self.families.insert(key, family);
+    }
+
+    /// Forget a controller, like when it's disconnected.
+    pub fn remove(&mut self, key: &K) -> Option<ControllerFamily> {
+        self.families.remove(key)
+    }
+
+    /// Returns the family of a controller.
+    pub fn family(&self, key: &K) -> ControllerFamily {
+        self.families
+            .get(key)
+            .copied()
+            .unwrap_or(ControllerFamily::Generic)
+    }
+
+    /// Returns the families of every known controller, like for a
+    /// [`BindingsContext`](crate::BindingsContext).
+    pub fn families(&self) -> impl Iterator<Item = ControllerFamily> + '_ {
+        self.families.values().copied()
+    }
+
+    /// Returns the full name of an input on a controller.
+    pub fn label<I: Into<Gamepad>>(&self, key: &K, input: I) -> &'static str {
+        input.into().family_label(self.family(key))
+    }
+
+    /// Returns the name printed on an input for a controller.
+    pub fn short_label<I: Into<Gamepad>>(&self, key: &K, input: I) -> &'static str {
+        input.into().family_short_label(self.family(key))
+    }
+
+    /// Returns an ID for the image of an input for a controller.
+    pub fn glyph<I: Into<Gamepad>>(&self, key: &K, input: I) -> String {
+        input.into().glyph(self.family(key))
+    }
+}
+
+impl<K: Eq + Hash> Default for ControllerFamilies<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "gilrs")]
+impl ControllerFamilies<gilrs::GamepadId> {
+    /// Detect the family of every gamepad connected to gilrs.
+    pub fn from_gilrs(gilrs: &gilrs::Gilrs) -> Self {
+        let mut families = Self::new();
+
+        for (id, gamepad) in gilrs.gamepads() {
+            families.insert(id, ControllerFamily::from_gilrs(&gamepad));
+        }
+
+        families
+    }
+}
+
+/// Returns the short name, full name and glyph name of buttons that differ
+/// between families. Other buttons, like the D-pad, use the same names
+/// everywhere.
+fn family_names(
+    button: GamepadButton,
+    family: ControllerFamily,
+) -> Option<(&'static str, &'static str, &'static str)> {
+    use ControllerFamily::*;
+    use GamepadButton::*;
+
+    let names = match (family, button) {
+        (Xbox | SteamDeck, A) => ("A", "A", "a"),
+        (Xbox | SteamDeck, B) => ("B", "B", "b"),
+        (Xbox | SteamDeck, X) => ("X", "X", "x"),
+        (Xbox | SteamDeck, Y) => ("Y", "Y", "y"),
+        (Xbox | SteamDeck, Select) => ("View", "View", "view"),
+        (Xbox | SteamDeck, Start) => ("Menu", "Menu", "menu"),
+
+        (Xbox, LeftShoulder) => ("LB", "Left Bumper", "lb"),
+        (Xbox, RightShoulder) => ("RB", "Right Bumper", "rb"),
+        (Xbox, LeftTrigger) => ("LT", "Left Trigger", "lt"),
+        (Xbox, RightTrigger) => ("RT", "Right Trigger", "rt"),
+        // "LS" and "RS" are already the sticks themselves.
+        (Xbox, LeftThumb) => ("LSB", "Left Stick Button", "lsb"),
+        (Xbox, RightThumb) => ("RSB", "Right Stick Button", "rsb"),
+
+        (PlayStation, A) => ("Cross", "Cross", "cross"),
+        (PlayStation, B) => ("Circle", "Circle", "circle"),
+        (PlayStation, X) => ("Square", "Square", "square"),
+        (PlayStation, Y) => ("Triangle", "Triangle", "triangle"),
+        (PlayStation, Select) => ("Create", "Create", "create"),
+        (PlayStation, Start) => ("Options", "Options", "options"),
+
+        (PlayStation | SteamDeck, LeftShoulder) => ("L1", "L1 Button", "l1"),
+        (PlayStation | SteamDeck, RightShoulder) => ("R1", "R1 Button", "r1"),
+        (PlayStation | SteamDeck, LeftTrigger) => ("L2", "L2 Trigger", "l2"),
+        (PlayStation | SteamDeck, RightTrigger) => ("R2", "R2 Trigger", "r2"),
+        (PlayStation | SteamDeck, LeftThumb) => ("L3", "L3 Button", "l3"),
+        (PlayStation | SteamDeck, RightThumb) => ("R3", "R3 Button", "r3"),
+
+        // Nintendo controllers swap A/B and X/Y compared to Xbox controllers.
+        (Nintendo, A) => ("B", "B", "b"),
+        (Nintendo, B) => ("A", "A", "a"),
+        (Nintendo, X) => ("Y", "Y", "y"),
+        (Nintendo, Y) => ("X", "X", "x"),
+        (Nintendo, LeftShoulder) => ("L", "L Button", "l"),
+        (Nintendo, RightShoulder) => ("R", "R Button", "r"),
+        (Nintendo, LeftTrigger) => ("ZL", "ZL Button", "zl"),
+        (Nintendo, RightTrigger) => ("ZR", "ZR Button", "zr"),
+        (Nintendo, LeftThumb) => ("LSB", "Left Stick Button", "ls"),
+        (Nintendo, RightThumb) => ("RSB", "Right Stick Button", "rs"),
+        (Nintendo, Select) => ("-", "Minus", "minus"),
+        (Nintendo, Start) => ("+", "Plus", "plus"),
+
+        _ => return None,
+    };
+
+    Some(names)
+}
+
+/// Returns the short and full names of an input on a family of controllers,
+/// or `None` if the family uses the input's generic labels.
+pub(super) fn family_specific_names(
+    input: Gamepad,
+    family: ControllerFamily,
+) -> Option<(&'static str, &'static str)> {
+    let button = match input {
+        Gamepad::Button(button) => button,
+        Gamepad::Axis1d(axis) => trigger_button(axis)?,
+        Gamepad::Axis2d(_) => return None,
+    };
+
+    family_names(button, family).map(|(short, long, _)| (short, long))
+}
+
+/// Triggers are both axes and buttons, and are labeled the same either way.
+fn trigger_button(axis: GamepadAxis1d) -> Option<GamepadButton> {
+    match axis {
+        GamepadAxis1d::LeftTrigger => Some(GamepadButton::LeftTrigger),
+        GamepadAxis1d::RightTrigger => Some(GamepadButton::RightTrigger),
+        _ => None,
+    }
+}
+
+/// Builds a glyph ID from an input's name, like `xbox/dpadup`.
+fn generic_glyph<I: std::fmt::Display>(input: I, family: ControllerFamily) -> String {
+    let name = input.to_string();
+    let name = name.strip_prefix("gamepad/").unwrap_or(&name);
+
+    format!("{}/{}", family.name(), name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn family_labels() {
+        assert_eq!(
+            GamepadButton::A.family_label(ControllerFamily::PlayStation),
+            "Cross"
+        );
+        assert_eq!(
+            GamepadButton::A.family_label(ControllerFamily::Nintendo),
+            "B"
+        );
+        assert_eq!(
+            GamepadButton::A.glyph(ControllerFamily::Nintendo),
+            "nintendo/b"
+        );
+        assert_eq!(
+            GamepadButton::DpadUp.glyph(ControllerFamily::Xbox),
+            "xbox/dpadup"
+        );
+        assert_eq!(
+            GamepadAxis1d::LeftTrigger.family_short_label(ControllerFamily::SteamDeck),
+            "L2"
+        );
+        assert_eq!(
+            GamepadButton::LeftShoulder.family_label(ControllerFamily::Xbox),
+            "Left Bumper"
+        );
+        assert_eq!(
+            GamepadButton::DpadUp.family_short_label(ControllerFamily::Xbox),
+            "D-Up"
+        );
+        assert_ne!(
+            GamepadButton::LeftThumb.family_short_label(ControllerFamily::Xbox),
+            crate::GamepadAxis2d::LeftStick.short_label()
+        );
+        assert_eq!(
+            GamepadAxis1d::LeftStickX.glyph(ControllerFamily::Generic),
+            "generic/leftstickx"
+        );
+    }
+
+    #[test]
+    fn per_player() {
+        let mut families = ControllerFamilies::new();
+        families.insert(0, ControllerFamily::detect(Some(0x054c), Some(0x0ce6), ""));
+        families.insert(
+            1,
+            ControllerFamily::detect(None, None, "Xbox Wireless Controller"),
+        );
+
+        assert_eq!(families.label(&0, GamepadButton::Y), "Triangle");
+        assert_eq!(families.label(&1, GamepadButton::Y), "Y");
+        assert_eq!(families.glyph(&2, GamepadButton::Start), "generic/start");
+    }
+}
@@ -0,0 +1,368 @@
+use super::{
+    Axis1d, Axis2d, Button, Gamepad, GamepadAxis1d, GamepadAxis2d, GamepadButton, Input,
+    KeyboardKey, Mouse, MouseAxis1d, MouseAxis2d, MouseButton,
+};
+
+/// Defines a method that returns a label for each input type from a table of
+/// `Variant => "Label",` entries.
+macro_rules! labels {
+    (
+        #[doc = $doc:literal]
+        fn $method:ident;
+
+        $( $ty:ident { $( $variant:ident => $label:literal, )* } )*
+    ) => {
+        $(
+            impl $ty {
+                #[doc = $doc]
+                pub fn $method(self) -> &'static str {
+                    match self {
+                        $( Self::$variant => $label, )*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Defines a label method for enums that wrap other inputs by calling the same
+/// method on the wrapped input.
+macro_rules! wrapper_labels {
+    (
+        #[doc = $doc:literal]
+        fn $method:ident;
+
+        $( $ty:ident { $( $variant:ident, )* } )*
+    ) => {
+        $(
+            impl $ty {
+                #[doc = $doc]
+                pub fn $method(self) -> &'static str {
+                    match self {
+                        $( Self::$variant(inner) => inner.$method(), )*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+labels! {
+    /// Returns an English name for this input, like "Left Shift".
+    fn label;
+
+    KeyboardKey {
+        A => "A",
+        B => "B",
+        C => "C",
+        D => "D",
+        E => "E",
+        F => "F",
+        G => "G",
+        H => "H",
+        I => "I",
+        J => "J",
+        K => "K",
+        L => "L",
+        M => "M",
+        N => "N",
+        O => "O",
+        P => "P",
+        Q => "Q",
+        R => "R",
+        S => "S",
+        T => "T",
+        U => "U",
+        V => "V",
+        W => "W",
+        X => "X",
+        Y => "Y",
+        Z => "Z",
+        Zero => "0",
+        One => "1",
+        Two => "2",
+        Three => "3",
+        Four => "4",
+        Five => "5",
+        Six => "6",
+        Seven => "7",
+        Eight => "8",
+        Nine => "9",
+        F1 => "F1",
+        F2 => "F2",
+        F3 => "F3",
+        F4 => "F4",
+        F5 => "F5",
+        F6 => "F6",
+        F7 => "F7",
+        F8 => "F8",
+        F9 => "F9",
+        F10 => "F10",
+        F11 => "F11",
+        F12 => "F12",
+        F13 => "F13",
+        F14 => "F14",
+        F15 => "F15",
+        F16 => "F16",
+        F17 => "F17",
+        F18 => "F18",
+        F19 => "F19",
+        F20 => "F20",
+        F21 => "F21",
+        F22 => "F22",
+        F23 => "F23",
+        F24 => "F24",
+        Tab => "Tab",
+        Return => "Enter",
+        Up => "Up Arrow",
+        Down => "Down Arrow",
+        Left => "Left Arrow",
+        Right => "Right Arrow",
+        Space => "Space",
+        LShift => "Left Shift",
+        RShift => "Right Shift",
+        LControl => "Left Ctrl",
+        RControl => "Right Ctrl",
+        LAlt => "Left Alt",
+        RAlt => "Right Alt",
+        LSuper => "Left Super",
+        RSuper => "Right Super",
+        Escape => "Escape",
+        Delete => "Delete",
+    }
+
+    MouseButton {
+        Button1 => "Left Mouse Button",
+        Button2 => "Right Mouse Button",
+        Button3 => "Middle Mouse Button",
+        Button4 => "Mouse Button 4",
+        Button5 => "Mouse Button 5",
+    }
+
+    MouseAxis1d {
+        WheelX => "Horizontal Scroll",
+        WheelY => "Scroll Wheel",
+        X => "Mouse X",
+        Y => "Mouse Y",
+    }
+
+    MouseAxis2d {
+        XY => "Mouse",
+    }
+
+    GamepadButton {
+        A => "A",
+        B => "B",
+        X => "X",
+        Y => "Y",
+        DpadUp => "D-Pad Up",
+        DpadDown => "D-Pad Down",
+        DpadLeft => "D-Pad Left",
+        DpadRight => "D-Pad Right",
+        LeftStickLeft => "Left Stick Left",
+        LeftStickRight => "Left Stick Right",
+        LeftStickUp => "Left Stick Up",
+        LeftStickDown => "Left Stick Down",
+        RightStickLeft => "Right Stick Left",
+        RightStickRight => "Right Stick Right",
+        RightStickUp => "Right Stick Up",
+        RightStickDown => "Right Stick Down",
+        LeftShoulder => "Left Shoulder",
+        RightShoulder => "Right Shoulder",
+        LeftTrigger => "Left Trigger",
+        RightTrigger => "Right Trigger",
+        LeftThumb => "Left Stick Button",
+        RightThumb => "Right Stick Button",
+        Select => "Select",
+        Start => "Start",
+    }
+
+    GamepadAxis1d {
+        LeftStickX => "Left Stick X",
+        LeftStickY => "Left Stick Y",
+        RightStickX => "Right Stick X",
+        RightStickY => "Right Stick Y",
+        LeftTrigger => "Left Trigger",
+        RightTrigger => "Right Trigger",
+        DpadX => "D-Pad X",
+        DpadY => "D-Pad Y",
+    }
+
+    GamepadAxis2d {
+        LeftStick => "Left Stick",
+        RightStick => "Right Stick",
+    }
+}
+
+wrapper_labels! {
+    /// Returns an English name for this input, like "Left Shift".
+    fn label;
+
+    Button { Keyboard, Mouse, Gamepad, }
+    Axis1d { Mouse, Gamepad, }
+    Axis2d { Mouse, Gamepad, }
+    Mouse { Button, Axis1d, Axis2d, }
+    Gamepad { Button, Axis1d, Axis2d, }
+    Input { Keyboard, Mouse, Gamepad, }
+}
+
+labels! {
+    /// Returns a short English name for this input, like "LShift".
+    fn short_label;
+
+    KeyboardKey {
+        A => "A",
+        B => "B",
+        C => "C",
+        D => "D",
+        E => "E",
+        F => "F",
+        G => "G",
+        H => "H",
+        I => "I",
+        J => "J",
+        K => "K",
+        L => "L",
+        M => "M",
+        N => "N",
+        O => "O",
+        P => "P",
+        Q => "Q",
+        R => "R",
+        S => "S",
+        T => "T",
+        U => "U",
+        V => "V",
+        W => "W",
+        X => "X",
+        Y => "Y",
+        Z => "Z",
+        Zero => "0",
+        One => "1",
+        Two => "2",
+        Three => "3",
+        Four => "4",
+        Five => "5",
+        Six => "6",
+        Seven => "7",
+        Eight => "8",
+        Nine => "9",
+        F1 => "F1",
+        F2 => "F2",
+        F3 => "F3",
+        F4 => "F4",
+        F5 => "F5",
+        F6 => "F6",
+        F7 => "F7",
+        F8 => "F8",
+        F9 => "F9",
+        F10 => "F10",
+        F11 => "F11",
+        F12 => "F12",
+        F13 => "F13",
+        F14 => "F14",
+        F15 => "F15",
+        F16 => "F16",
+        F17 => "F17",
+        F18 => "F18",
+        F19 => "F19",
+        F20 => "F20",
+        F21 => "F21",
+        F22 => "F22",
+        F23 => "F23",
+        F24 => "F24",
+        Tab => "Tab",
+        Return => "Enter",
+        Up => "Up",
+        Down => "Down",
+        Left => "Left",
+        Right => "Right",
+        Space => "Space",
+        LShift => "LShift",
+        RShift => "RShift",
+        LControl => "LCtrl",
+        RControl => "RCtrl",
+        LAlt => "LAlt",
+        RAlt => "RAlt",
+        LSuper => "LSuper",
+        RSuper => "RSuper",
+        Escape => "Esc",
+        Delete => "Del",
+    }
+
+    MouseButton {
+        Button1 => "LMB",
+        Button2 => "RMB",
+        Button3 => "MMB",
+        Button4 => "MB4",
+        Button5 => "MB5",
+    }
+
+    MouseAxis1d {
+        WheelX => "Scroll X",
+        WheelY => "Scroll",
+        X => "Mouse X",
+        Y => "Mouse Y",
+    }
+
+    MouseAxis2d {
+        XY => "Mouse",
+    }
+
+    GamepadButton {
+        A => "A",
+        B => "B",
+        X => "X",
+        Y => "Y",
+        DpadUp => "D-Up",
+        DpadDown => "D-Down",
+        DpadLeft => "D-Left",
+        DpadRight => "D-Right",
+        LeftStickLeft => "LS Left",
+        LeftStickRight => "LS Right",
+        LeftStickUp => "LS Up",
+        LeftStickDown => "LS Down",
+        RightStickLeft => "RS Left",
+        RightStickRight => "RS Right",
+        RightStickUp => "RS Up",
+        RightStickDown => "RS Down",
+        // Generic names follow the Xbox layout that the variants are named
+        // after. Other families' names come from `DisplayNames::family_short`.
+        LeftShoulder => "LB",
+        RightShoulder => "RB",
+        LeftTrigger => "LT",
+        RightTrigger => "RT",
+        LeftThumb => "LSB",
+        RightThumb => "RSB",
+        Select => "Select",
+        Start => "Start",
+    }
+
+    GamepadAxis1d {
+        LeftStickX => "LS X",
+        LeftStickY => "LS Y",
+        RightStickX => "RS X",
+        RightStickY => "RS Y",
+        LeftTrigger => "LT",
+        RightTrigger => "RT",
+        DpadX => "D-Pad X",
+        DpadY => "D-Pad Y",
+    }
+
+    GamepadAxis2d {
+        LeftStick => "LS",
+        RightStick => "RS",
+    }
+}
+
+wrapper_labels! {
+    /// Returns a short English name for this input, like "LShift".
+    fn short_label;
+
+    Button { Keyboard, Mouse, Gamepad, }
+    Axis1d { Mouse, Gamepad, }
+    Axis2d { Mouse, Gamepad, }
+    Mouse { Button, Axis1d, Axis2d, }
+    Gamepad { Button, Axis1d, Axis2d, }
+    Input { Keyboard, Mouse, Gamepad, }
+}
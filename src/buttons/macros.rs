@@ -7,6 +7,14 @@ macro_rules! wrapper_enum {
             $( $variant($inner), )*
         }
 
+        impl $enum_name {
+            /// Returns every value, in declaration order.
+            pub fn all() -> impl Iterator<Item = Self> {
+                std::iter::empty()
+                    $( .chain($inner::all().map(Self::$variant)) )*
+            }
+        }
+
         $(
             impl From<$inner> for $enum_name {
                 fn from(value: $inner) -> Self {
@@ -66,6 +74,16 @@ macro_rules! keyboard {
             $( $variant, )*
         }
 
+        impl KeyboardKey {
+            /// Every key, in declaration order.
+            pub const ALL: &'static [Self] = &[ $( Self::$variant, )* ];
+
+            /// Returns every key, in declaration order.
+            pub fn all() -> impl Iterator<Item = Self> {
+                Self::ALL.iter().copied()
+            }
+        }
+
         impl std::str::FromStr for KeyboardKey {
             type Err = anyhow::Error;
 
@@ -127,6 +145,16 @@ macro_rules! define_device {
                 $( $variant, )*
             }
 
+            impl $enum {
+                /// Every value, in declaration order.
+                pub const ALL: &'static [Self] = &[ $( Self::$variant, )* ];
+
+                /// Returns every value, in declaration order.
+                pub fn all() -> impl Iterator<Item = Self> {
+                    Self::ALL.iter().copied()
+                }
+            }
+
             impl std::fmt::Display for $enum {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     let button = match self {
@@ -154,6 +182,22 @@ macro_rules! define_device {
             $( $enum_variant($enum), )*
         }
 
+        impl $input {
+            /// Returns every input on this device, in declaration order.
+            pub fn all() -> impl Iterator<Item = Self> {
+                std::iter::empty()
+                    $( .chain($enum::all().map(Self::$enum_variant)) )*
+            }
+        }
+
+        $(
+            impl From<$enum> for $input {
+                fn from(value: $enum) -> Self {
+                    Self::$enum_variant(value)
+                }
+            }
+        )*
+
         impl std::fmt::Display for $input {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
@@ -3,6 +3,11 @@
 #[macro_use]
 mod macros;
 
+mod family;
+mod glyphs;
+mod labels;
+mod names;
+
 #[cfg(feature = "sdl2")]
 mod sdl2;
 
@@ -12,6 +17,10 @@ mod sdl3;
 #[cfg(feature = "winit")]
 use winit::event::MouseButton as WinitMouseButton;
 
+pub use family::*;
+pub use glyphs::*;
+pub use names::*;
+
 wrapper_enum! {
     Button {
         Keyboard(KeyboardKey, keyboard),
@@ -121,6 +130,8 @@ keyboard! {
     RControl(rightctrl),
     LAlt(leftalt),
     RAlt(rightalt),
+    LSuper(leftsuper),
+    RSuper(rightsuper),
     Escape(escape),
     Delete(delete),
 }
@@ -204,6 +215,8 @@ keyboard_winit! {
     RControl => ControlRight,
     LAlt => AltLeft,
     RAlt => AltRight,
+    LSuper => SuperLeft,
+    RSuper => SuperRight,
     Escape => Escape,
     Delete => Delete,
 }
@@ -258,6 +271,82 @@ define_device! {
     })
 }
 
+/// The kind of device that an input comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DeviceClass {
+    KeyboardMouse,
+    Gamepad,
+}
+
+impl Input {
+    /// Returns the kind of device that this input comes from.
+    pub fn device_class(self) -> DeviceClass {
+        match self {
+            Self::Keyboard(_) | Self::Mouse(_) => DeviceClass::KeyboardMouse,
+            Self::Gamepad(_) => DeviceClass::Gamepad,
+        }
+    }
+}
+
+impl Button {
+    /// Returns the kind of device that this button comes from.
+    pub fn device_class(self) -> DeviceClass {
+        Input::from(self).device_class()
+    }
+}
+
+impl Axis1d {
+    /// Returns the kind of device that this axis comes from.
+    pub fn device_class(self) -> DeviceClass {
+        Input::from(self).device_class()
+    }
+}
+
+impl Axis2d {
+    /// Returns the kind of device that this axis comes from.
+    pub fn device_class(self) -> DeviceClass {
+        Input::from(self).device_class()
+    }
+}
+
+impl From<Button> for Input {
+    fn from(value: Button) -> Self {
+        match value {
+            Button::Keyboard(key) => Self::Keyboard(key),
+            Button::Mouse(button) => Self::Mouse(Mouse::Button(button)),
+            Button::Gamepad(button) => Self::Gamepad(Gamepad::Button(button)),
+        }
+    }
+}
+
+impl From<Axis1d> for Input {
+    fn from(value: Axis1d) -> Self {
+        match value {
+            Axis1d::Mouse(axis) => Self::Mouse(Mouse::Axis1d(axis)),
+            Axis1d::Gamepad(axis) => Self::Gamepad(Gamepad::Axis1d(axis)),
+        }
+    }
+}
+
+impl From<Axis2d> for Input {
+    fn from(value: Axis2d) -> Self {
+        match value {
+            Axis2d::Mouse(axis) => Self::Mouse(Mouse::Axis2d(axis)),
+            Axis2d::Gamepad(axis) => Self::Gamepad(Gamepad::Axis2d(axis)),
+        }
+    }
+}
+
+impl GamepadAxis2d {
+    /// Returns the X and Y axes that make up this stick.
+    pub fn components(self) -> (GamepadAxis1d, GamepadAxis1d) {
+        match self {
+            Self::LeftStick => (GamepadAxis1d::LeftStickX, GamepadAxis1d::LeftStickY),
+            Self::RightStick => (GamepadAxis1d::RightStickX, GamepadAxis1d::RightStickY),
+        }
+    }
+}
+
 #[cfg(feature = "gilrs")]
 impl TryFrom<gilrs::Button> for GamepadButton {
     type Error = anyhow::Error;
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::glyphs::family_specific_names;
+use super::{ControllerFamily, Gamepad, GamepadButton, Input};
+
+/// A table of display names for inputs that replaces keybee's built-in
+/// English labels, like a translation for one language.
+///
+/// Inputs without an entry fall back to [`Input::label`] and
+/// [`Input::short_label`]. With serde, the table is a map from input names to
+/// their display names, with an optional `families` key for names that only
+/// apply to one [`ControllerFamily`]:
+///
+/// ```json
+/// {
+///     "keyboard/leftshift": { "short": "Maj G", "long": "Maj gauche" },
+///     "keyboard/space": { "long": "Espace" },
+///     "families": {
+///         "playstation": { "gamepad/a": { "long": "Croix" } }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayNames {
+    #[serde(flatten)]
+    names: BTreeMap<Input, DisplayName>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    families: BTreeMap<ControllerFamily, BTreeMap<Input, DisplayName>>,
+}
+
+/// The names shown for one input in a [`DisplayNames`] table. Either name can
+/// be left out to keep the built-in one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayName {
+    /// A short name, like "LShift", for places without much room.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short: Option<String>,
+
+    /// The full name, like "Left Shift".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long: Option<String>,
+}
+
+impl DisplayName {
+    pub fn new<S: Into<String>, L: Into<String>>(short: S, long: L) -> Self {
+        Self {
+            short: Some(short.into()),
+            long: Some(long.into()),
+        }
+    }
+}
+
+impl DisplayNames {
+    pub const fn new() -> Self {
+        Self {
+            names: BTreeMap::new(),
+            families: BTreeMap::new(),
+        }
+    }
+
+    /// Set the names shown for an input, replacing any it already had.
+    pub fn insert<I: Into<Input>>(&mut self, input: I, name: DisplayName) {
+        self.names.insert(input.into(), name);
+    }
+
+    /// Set the names shown for an input on one family of controllers, like
+    /// "Croix" for `gamepad/a` on PlayStation controllers.
+    pub fn insert_family<I: Into<Input>>(
+        &mut self,
+        family: ControllerFamily,
+        input: I,
+        name: DisplayName,
+    ) {
+        self.families
+            .entry(family)
+            .or_default()
+            .insert(input.into(), name);
+    }
+
+    /// Returns the overridden names for an input, if it has any.
+    pub fn get<I: Into<Input>>(&self, input: I) -> Option<&DisplayName> {
+        self.names.get(&input.into())
+    }
+
+    /// Add every entry from `other`, replacing names that are already set.
+    /// Useful for layering a partial translation over a complete one.
+    pub fn merge(&mut self, other: DisplayNames) {
+        self.names.extend(other.names);
+
+        for (family, names) in other.families {
+            self.families.entry(family).or_default().extend(names);
+        }
+    }
+
+    /// Returns the full name for an input, like "Left Shift".
+    pub fn long<I: Into<Input>>(&self, input: I) -> &str {
+        let input = input.into();
+
+        self.names
+            .get(&input)
+            .and_then(|name| name.long.as_deref())
+            .unwrap_or(input.label())
+    }
+
+    /// Returns the short name for an input, like "LShift".
+    pub fn short<I: Into<Input>>(&self, input: I) -> &str {
+        let input = input.into();
+
+        self.names
+            .get(&input)
+            .and_then(|name| name.short.as_deref())
+            .unwrap_or(input.short_label())
+    }
+
+    /// Returns the full name for an input on a family of controllers, like
+    /// "Cross" for `gamepad/a` on PlayStation controllers.
+    ///
+    /// Names set for the family with [`DisplayNames::insert_family`] come
+    /// first. Face buttons then use keybee's built-in name for the family,
+    /// since it matches the symbol printed on the button. Other inputs use the
+    /// names set with [`DisplayNames::insert`] before the built-in family
+    /// names, so a translation doesn't need a `families` entry for every
+    /// controller.
+    pub fn family_long<I: Into<Input>>(&self, family: ControllerFamily, input: I) -> &str {
+        self.family_name(
+            family,
+            input.into(),
+            |name| name.long.as_deref(),
+            |(_, long)| long,
+            Input::label,
+        )
+    }
+
+    /// Returns the short name for an input on a family of controllers, like
+    /// "L1" for `gamepad/leftshoulder` on PlayStation controllers. Names are
+    /// looked up in the same order as [`DisplayNames::family_long`].
+    pub fn family_short<I: Into<Input>>(&self, family: ControllerFamily, input: I) -> &str {
+        self.family_name(
+            family,
+            input.into(),
+            |name| name.short.as_deref(),
+            |(short, _)| short,
+            Input::short_label,
+        )
+    }
+
+    fn family_name<P>(
+        &self,
+        family: ControllerFamily,
+        input: Input,
+        pick: P,
+        pick_built_in: fn((&'static str, &'static str)) -> &'static str,
+        generic: fn(Input) -> &'static str,
+    ) -> &str
+    where
+        P: Fn(&DisplayName) -> Option<&str>,
+    {
+        let overridden = self
+            .families
+            .get(&family)
+            .and_then(|names| names.get(&input))
+            .and_then(&pick);
+
+        if let Some(name) = overridden {
+            return name;
+        }
+
+        let built_in = match input {
+            Input::Gamepad(gamepad) => family_specific_names(gamepad, family).map(pick_built_in),
+            _ => None,
+        };
+
+        let is_face_button = matches!(
+            input,
+            Input::Gamepad(Gamepad::Button(
+                GamepadButton::A | GamepadButton::B | GamepadButton::X | GamepadButton::Y
+            ))
+        );
+
+        if let (true, Some(name)) = (is_face_button, built_in) {
+            return name;
+        }
+
+        self.names
+            .get(&input)
+            .and_then(pick)
+            .or(built_in)
+            .unwrap_or_else(|| generic(input))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Axis2d, Button, GamepadAxis2d, GamepadButton, KeyboardKey};
+
+    use super::*;
+
+    #[test]
+    fn fall_back_to_labels() {
+        let names: DisplayNames = serde_json::from_str(
+            r#"{
+                "keyboard/leftshift": { "short": "Maj G", "long": "Maj gauche" },
+                "keyboard/space": { "long": "Espace" }
+            }"#,
+        )
+        .unwrap();
+
+        let shoulder = Button::from(GamepadButton::LeftShoulder);
+
+        assert_eq!(names.long(KeyboardKey::LShift), "Maj gauche");
+        assert_eq!(names.short(KeyboardKey::LShift), "Maj G");
+        assert_eq!(names.long(KeyboardKey::Space), "Espace");
+        assert_eq!(names.short(KeyboardKey::Space), "Space");
+        assert_eq!(names.long(shoulder), "Left Shoulder");
+        assert_eq!(names.short(shoulder), "LB");
+    }
+
+    #[test]
+    fn family_names() {
+        let names: DisplayNames = serde_json::from_str(
+            r#"{
+                "gamepad/a": { "long": "Bouton A" },
+                "gamepad/select": { "short": "Sél", "long": "Sélection" },
+                "gamepad/leftstick": { "long": "Stick gauche" },
+                "families": {
+                    "playstation": { "gamepad/a": { "long": "Croix" } }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let a = Button::from(GamepadButton::A);
+        let select = Button::from(GamepadButton::Select);
+        let shoulder = Button::from(GamepadButton::LeftShoulder);
+        let stick = Axis2d::from(GamepadAxis2d::LeftStick);
+
+        assert_eq!(names.family_long(ControllerFamily::PlayStation, a), "Croix");
+        assert_eq!(
+            names.family_short(ControllerFamily::PlayStation, a),
+            "Cross"
+        );
+        assert_eq!(names.family_long(ControllerFamily::Nintendo, a), "B");
+        assert_eq!(names.family_long(ControllerFamily::Generic, a), "Bouton A");
+        assert_eq!(
+            names.family_long(ControllerFamily::Xbox, select),
+            "Sélection"
+        );
+        assert_eq!(names.family_short(ControllerFamily::Xbox, select), "Sél");
+        assert_eq!(
+            names.family_long(ControllerFamily::Xbox, shoulder),
+            "Left Bumper"
+        );
+        assert_eq!(names.family_short(ControllerFamily::Xbox, shoulder), "LB");
+        assert_eq!(
+            names.family_short(ControllerFamily::PlayStation, shoulder),
+            "L1"
+        );
+        assert_eq!(
+            names.family_long(ControllerFamily::PlayStation, stick),
+            "Stick gauche"
+        );
+
+        let json = serde_json::to_string(&names).unwrap();
+        assert_eq!(serde_json::from_str::<DisplayNames>(&json).unwrap(), names);
+    }
+}
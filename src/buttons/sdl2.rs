@@ -1,7 +1,8 @@
+use sdl2::controller::GameController;
 use sdl2::keyboard::Scancode;
 use sdl2::mouse::MouseButton as SdlMouseButton;
 
-use super::{KeyboardKey, MouseButton};
+use super::{ControllerFamily, KeyboardKey, MouseButton};
 
 impl MouseButton {
     pub fn from_sdl2(sdl: SdlMouseButton) -> Option<Self> {
@@ -97,6 +98,8 @@ impl KeyboardKey {
             Scancode::RCtrl => Some(Self::RControl),
             Scancode::LAlt => Some(Self::LAlt),
             Scancode::RAlt => Some(Self::RAlt),
+            Scancode::LGui => Some(Self::LSuper),
+            Scancode::RGui => Some(Self::RSuper),
             Scancode::Escape => Some(Self::Escape),
             Scancode::Delete => Some(Self::Delete),
 
@@ -104,3 +107,14 @@ impl KeyboardKey {
         }
     }
 }
+
+impl ControllerFamily {
+    /// Detect the family of an SDL2 game controller.
+    pub fn from_sdl2(controller: &GameController) -> Self {
+        Self::detect(
+            controller.vendor_id(),
+            controller.product_id(),
+            &controller.name(),
+        )
+    }
+}
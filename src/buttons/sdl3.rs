@@ -1,7 +1,8 @@
+use sdl3::gamepad::{Gamepad, GamepadType};
 use sdl3::keyboard::Scancode;
 use sdl3::mouse::MouseButton as SdlMouseButton;
 
-use super::{KeyboardKey, MouseButton};
+use super::{ControllerFamily, KeyboardKey, MouseButton};
 
 impl MouseButton {
     pub fn from_sdl3(sdl: SdlMouseButton) -> Option<Self> {
@@ -97,6 +98,8 @@ impl KeyboardKey {
             Scancode::RCtrl => Some(Self::RControl),
             Scancode::LAlt => Some(Self::LAlt),
             Scancode::RAlt => Some(Self::RAlt),
+            Scancode::LGui => Some(Self::LSuper),
+            Scancode::RGui => Some(Self::RSuper),
             Scancode::Escape => Some(Self::Escape),
             Scancode::Delete => Some(Self::Delete),
 
@@ -104,3 +107,23 @@ impl KeyboardKey {
         }
     }
 }
+
+impl ControllerFamily {
+    /// Detect the family of an SDL3 gamepad, using the type SDL reports when
+    /// it knows one.
+    pub fn from_sdl3(gamepad: &Gamepad) -> Self {
+        match gamepad.r#type() {
+            GamepadType::Xbox360 | GamepadType::XboxOne => Self::Xbox,
+            GamepadType::PS3 | GamepadType::PS4 | GamepadType::PS5 => Self::PlayStation,
+            GamepadType::NintendoSwitchPro
+            | GamepadType::NintendoSwitchJoyconLeft
+            | GamepadType::NintendoSwitchJoyconRight
+            | GamepadType::NintendoSwitchJoyconPair => Self::Nintendo,
+            _ => Self::detect(
+                gamepad.vendor_id(),
+                gamepad.product_id(),
+                &gamepad.name().unwrap_or_default(),
+            ),
+        }
+    }
+}
@@ -17,7 +17,7 @@ mod sdl3;
 /// By enabling the `winit` or `gilrs` features, keybee supports converting
 /// events from those libraries to this event type through the [`TryFrom`] and
 /// [`TryInto`] traits.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
 pub enum Event {
     ButtonPressed(Button),
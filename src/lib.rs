@@ -24,8 +24,7 @@ let gameplay = session.create_action_set("gameplay");
 let jump = gameplay.create_action("jump", EventAction);
 let movement = gameplay.create_action("movement", Clamped(Axis2dAction));
 
-// Keybee will have support for deserializing bindings from files, but for now,
-// this isn't quite done.
+// Bindings can be deserialized with serde from formats like JSON or YAML.
 session.use_bindings(todo!("load bindings from somewhere"));
 
 loop {
@@ -1,27 +1,40 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::bindings::Binding;
+use crate::buttons::Input;
 
 pub(super) struct BindingsCache {
     bindings: HashMap<String, Vec<Binding>>,
+    set_inputs: HashMap<String, HashSet<Input>>,
 }
 
 impl BindingsCache {
     pub fn new() -> Self {
         Self {
             bindings: HashMap::new(),
+            set_inputs: HashMap::new(),
         }
     }
 
     pub fn clear(&mut self) {
         self.bindings.clear();
+        self.set_inputs.clear();
     }
 
     pub fn get(&self, action_name: &str) -> Option<&[Binding]> {
         self.bindings.get(action_name).map(|vec| vec.as_slice())
     }
 
-    pub fn insert(&mut self, action_name: String, bindings: Vec<Binding>) {
-        self.bindings.insert(action_name, bindings);
+    /// Returns every input bound to an action in the given action set.
+    pub fn set_inputs(&self, set_name: &str) -> Option<&HashSet<Input>> {
+        self.set_inputs.get(set_name)
+    }
+
+    pub fn insert(&mut self, set_name: &str, action_name: &str, bindings: Vec<Binding>) {
+        let set_inputs = self.set_inputs.entry(set_name.to_owned()).or_default();
+        set_inputs.extend(bindings.iter().flat_map(Binding::inputs));
+
+        let full_name = format!("{}/{}", set_name, action_name);
+        self.bindings.insert(full_name, bindings);
     }
 }
@@ -0,0 +1,367 @@
+use std::collections::HashSet;
+
+use crate::bindings::{
+    Axis1dBinding, AxisDirection, Binding, ChordBinding, ThresholdBinding, ThresholdSource,
+};
+use crate::buttons::{Axis1d, Axis2d, Button, DeviceClass, Gamepad, Input, KeyboardKey};
+use crate::event::Event;
+use crate::state::{InputState, ACTIVE_AXIS_THRESHOLD};
+
+/// Configures how [`Session::begin_capture`](super::Session::begin_capture)
+/// listens for a new binding.
+#[derive(Debug, Clone)]
+pub struct CaptureOptions {
+    devices: Vec<DeviceClass>,
+    cancel: Vec<Button>,
+    excluded: Vec<Button>,
+    chords: bool,
+    axes: AxisCapture,
+    axis_threshold: f32,
+}
+
+/// Controls what kind of binding is captured when a gamepad axis is pushed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AxisCapture {
+    /// Ignore axes entirely.
+    Ignore,
+
+    /// Capture a [`ThresholdBinding`] for the direction the axis was pushed,
+    /// for binding axes to button actions.
+    #[default]
+    Threshold,
+
+    /// Capture an [`Axis1dBinding::Axis`] that reads positive in the direction
+    /// the axis was pushed.
+    Axis,
+}
+
+/// The outcome of a capture started with
+/// [`Session::begin_capture`](super::Session::begin_capture).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureResult {
+    /// The player pressed a button or pushed an axis.
+    Captured(Binding),
+
+    /// The player pressed one of the cancel buttons.
+    Canceled,
+}
+
+impl CaptureOptions {
+    /// Create options that accept every device, cancel with Escape, capture
+    /// single buttons and turn axes pushed past 0.5 into threshold bindings.
+    pub fn new() -> Self {
+        Self {
+            devices: vec![DeviceClass::KeyboardMouse, DeviceClass::Gamepad],
+            cancel: vec![Button::Keyboard(KeyboardKey::Escape)],
+            excluded: Vec::new(),
+            chords: false,
+            axes: AxisCapture::Threshold,
+            axis_threshold: 0.5,
+        }
+    }
+
+    /// Only capture inputs from the given kinds of devices.
+    pub fn with_devices<I: IntoIterator<Item = DeviceClass>>(self, devices: I) -> Self {
+        Self {
+            devices: devices.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Set the buttons that cancel the capture instead of being captured.
+    pub fn with_cancel<I: IntoIterator<Item = Button>>(self, cancel: I) -> Self {
+        Self {
+            cancel: cancel.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Set buttons that are ignored while capturing.
+    pub fn with_excluded<I: IntoIterator<Item = Button>>(self, excluded: I) -> Self {
+        Self {
+            excluded: excluded.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Allow capturing chords. A button pressed while another is held is
+    /// captured as a [`ChordBinding`], and a button released without pressing
+    /// another is captured on its own.
+    pub fn with_chords(self, chords: bool) -> Self {
+        Self { chords, ..self }
+    }
+
+    /// Set what kind of binding is captured from gamepad axes.
+    pub fn with_axes(self, axes: AxisCapture) -> Self {
+        Self { axes, ..self }
+    }
+
+    /// Set how far a gamepad axis has to be pushed before it's captured.
+    pub fn with_axis_threshold(self, axis_threshold: f32) -> Self {
+        Self {
+            axis_threshold,
+            ..self
+        }
+    }
+
+    fn allows(&self, input: Input) -> bool {
+        self.devices.contains(&input.device_class())
+    }
+}
+
+impl Default for CaptureOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The state of a capture in progress.
+pub(super) struct Capture {
+    options: CaptureOptions,
+    held: Option<Button>,
+    ignored: HashSet<Axis1d>,
+    result: Option<CaptureResult>,
+}
+
+impl Capture {
+    pub fn new(options: CaptureOptions, input: &InputState) -> Self {
+        // Axes that are already pushed when the capture starts have to return
+        // to center before they can be captured.
+        let ignored = input
+            .active_inputs()
+            .into_iter()
+            .filter_map(|input| match input {
+                Input::Gamepad(Gamepad::Axis1d(axis)) => Some(Axis1d::Gamepad(axis)),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            options,
+            held: None,
+            ignored,
+            result: None,
+        }
+    }
+
+    pub fn result(&self) -> Option<CaptureResult> {
+        self.result
+    }
+
+    /// Look at an event that was just applied to `input`. Returns true if this
+    /// event finished the capture.
+    pub fn handle_event(&mut self, event: &Event, input: &InputState) -> bool {
+        if self.result.is_some() {
+            return false;
+        }
+
+        self.result = match *event {
+            Event::ButtonPressed(button) => self.press(button),
+            Event::ButtonReleased(button) if self.held == Some(button) => {
+                Some(CaptureResult::Captured(Binding::Button(button)))
+            }
+            Event::Axis1dChanged(axis @ Axis1d::Gamepad(_), _) => self.axis(axis, input),
+            Event::Axis2dChanged(Axis2d::Gamepad(stick), _) => {
+                let (x, y) = stick.components();
+                self.axis(Axis1d::Gamepad(x), input)
+                    .or_else(|| self.axis(Axis1d::Gamepad(y), input))
+            }
+            _ => None,
+        };
+
+        self.result.is_some()
+    }
+
+    fn press(&mut self, button: Button) -> Option<CaptureResult> {
+        if self.options.cancel.contains(&button) {
+            return Some(CaptureResult::Canceled);
+        }
+
+        if self.options.excluded.contains(&button) || !self.options.allows(button.into()) {
+            return None;
+        }
+
+        if !self.options.chords {
+            return Some(CaptureResult::Captured(Binding::Button(button)));
+        }
+
+        match self.held {
+            Some(modifier) if modifier != button => {
+                Some(CaptureResult::Captured(Binding::Chord(ChordBinding {
+                    modifier,
+                    button,
+                })))
+            }
+            _ => {
+                self.held = Some(button);
+                None
+            }
+        }
+    }
+
+    fn axis(&mut self, axis: Axis1d, input: &InputState) -> Option<CaptureResult> {
+        let value = input.get_axis1d(axis);
+
+        if self.ignored.contains(&axis) {
+            if value.abs() <= ACTIVE_AXIS_THRESHOLD {
+                self.ignored.remove(&axis);
+            }
+
+            return None;
+        }
+
+        if !self.options.allows(axis.into()) || value.abs() < self.options.axis_threshold {
+            return None;
+        }
+
+        let direction = if value > 0.0 {
+            AxisDirection::Positive
+        } else {
+            AxisDirection::Negative
+        };
+
+        let binding = match self.options.axes {
+            AxisCapture::Ignore => return None,
+            AxisCapture::Threshold => Binding::Threshold(ThresholdBinding {
+                source: ThresholdSource::Axis1d(axis, direction),
+                press: self.options.axis_threshold,
+                release: self.options.axis_threshold / 2.0,
+            }),
+            AxisCapture::Axis => Binding::Axis1d(Axis1dBinding::Axis {
+                axis,
+                sensitivity: value.signum(),
+            }),
+        };
+
+        Some(CaptureResult::Captured(binding))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ActionSetBindings, Bindings, BoolAction, GamepadAxis1d, GamepadAxis2d, GamepadButton,
+        Session,
+    };
+
+    use super::*;
+
+    fn press(session: &mut Session, button: impl Into<Button>) {
+        session.handle_event(Event::ButtonPressed(button.into()));
+    }
+
+    fn release(session: &mut Session, button: impl Into<Button>) {
+        session.handle_event(Event::ButtonReleased(button.into()));
+    }
+
+    fn captured(binding: impl Into<Binding>) -> Option<CaptureResult> {
+        Some(CaptureResult::Captured(binding.into()))
+    }
+
+    #[test]
+    fn capture_button() {
+        let mut session = Session::new();
+        let gameplay = session.create_action_set("gameplay");
+        let jump = gameplay.create_action("jump", BoolAction);
+
+        let mut bindings = ActionSetBindings::new();
+        bindings.insert("jump", vec![KeyboardKey::Space.into()]);
+        let mut all = Bindings::new();
+        all.insert("gameplay", bindings);
+        session.use_bindings(all);
+
+        session.begin_capture(CaptureOptions::new());
+        assert!(session.is_capturing());
+
+        press(&mut session, KeyboardKey::Space);
+        assert!(!jump.get());
+        assert_eq!(session.poll_capture(), captured(KeyboardKey::Space));
+        assert!(!session.is_capturing());
+
+        // The captured button is ignored until it's released.
+        session.end_update();
+        assert!(!jump.get());
+        release(&mut session, KeyboardKey::Space);
+        press(&mut session, KeyboardKey::Space);
+        assert!(jump.get());
+    }
+
+    #[test]
+    fn cancel_and_filter() {
+        let mut session = Session::new();
+
+        session.begin_capture(
+            CaptureOptions::new()
+                .with_devices([DeviceClass::Gamepad])
+                .with_excluded([Button::Gamepad(GamepadButton::Start)]),
+        );
+
+        press(&mut session, KeyboardKey::Q);
+        press(&mut session, GamepadButton::Start);
+        assert_eq!(session.poll_capture(), None);
+
+        press(&mut session, KeyboardKey::Escape);
+        assert_eq!(session.poll_capture(), Some(CaptureResult::Canceled));
+    }
+
+    #[test]
+    fn capture_chord() {
+        let mut session = Session::new();
+        let options = CaptureOptions::new().with_chords(true);
+
+        session.begin_capture(options.clone());
+        press(&mut session, KeyboardKey::LControl);
+        assert_eq!(session.poll_capture(), None);
+        press(&mut session, KeyboardKey::S);
+        assert_eq!(
+            session.poll_capture(),
+            captured(ChordBinding {
+                modifier: KeyboardKey::LControl.into(),
+                button: KeyboardKey::S.into(),
+            })
+        );
+
+        release(&mut session, KeyboardKey::S);
+        release(&mut session, KeyboardKey::LControl);
+
+        session.begin_capture(options);
+        press(&mut session, KeyboardKey::LShift);
+        release(&mut session, KeyboardKey::LShift);
+        assert_eq!(session.poll_capture(), captured(KeyboardKey::LShift));
+    }
+
+    #[test]
+    fn capture_axis_direction() {
+        let mut session = Session::new();
+        let stick = Axis2d::Gamepad(GamepadAxis2d::LeftStick);
+        let left_y = Axis1d::Gamepad(GamepadAxis1d::LeftStickY);
+        let trigger = Axis1d::Gamepad(GamepadAxis1d::RightTrigger);
+
+        // Axes already pushed when the capture starts have to be let go first.
+        session.handle_event(Event::Axis1dChanged(trigger, 1.0));
+        session.begin_capture(CaptureOptions::new());
+        session.handle_event(Event::Axis1dChanged(trigger, 0.9));
+        assert_eq!(session.poll_capture(), None);
+
+        session.handle_event(Event::Axis2dChanged(stick, [0.1, -0.8]));
+        assert_eq!(
+            session.poll_capture(),
+            captured(ThresholdBinding {
+                source: ThresholdSource::Axis1d(left_y, AxisDirection::Negative),
+                press: 0.5,
+                release: 0.25,
+            })
+        );
+
+        session.handle_event(Event::Axis2dChanged(stick, [0.0, 0.0]));
+        session.begin_capture(CaptureOptions::new().with_axes(AxisCapture::Axis));
+        session.handle_event(Event::Axis2dChanged(stick, [0.0, -0.8]));
+        assert_eq!(
+            session.poll_capture(),
+            captured(Axis1dBinding::Axis {
+                axis: left_y,
+                sensitivity: -1.0,
+            })
+        );
+    }
+}
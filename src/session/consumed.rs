@@ -0,0 +1,58 @@
+use std::collections::HashSet;
+
+use crate::bindings::Binding;
+use crate::buttons::Input;
+
+/// The inputs that an action set can't use because a higher priority action
+/// set is using them.
+///
+/// This is worked out every time an action is read, so each action keeps one
+/// around and clears it instead of allocating a new one.
+pub(super) struct Consumed {
+    all: bool,
+    inputs: HashSet<Input>,
+}
+
+impl Consumed {
+    pub fn new() -> Self {
+        Self {
+            all: false,
+            inputs: HashSet::new(),
+        }
+    }
+
+    /// Forget every consumed input, keeping the allocated space.
+    pub fn clear(&mut self) {
+        self.all = false;
+        self.inputs.clear();
+    }
+
+    /// Mark every input as consumed.
+    pub fn consume_all(&mut self) {
+        self.all = true;
+    }
+
+    pub fn extend<'a, I>(&mut self, inputs: I)
+    where
+        I: IntoIterator<Item = &'a Input>,
+    {
+        if !self.all {
+            self.inputs.extend(inputs);
+        }
+    }
+
+    /// Tells whether the given binding reads from any consumed input.
+    pub fn hides(&self, binding: &Binding) -> bool {
+        if self.all {
+            return true;
+        }
+
+        let mut hidden = false;
+
+        if !self.inputs.is_empty() {
+            binding.for_each_input(|input| hidden |= self.inputs.contains(&input));
+        }
+
+        hidden
+    }
+}
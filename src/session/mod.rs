@@ -1,19 +1,34 @@
 mod bindings_cache;
+mod capture;
+mod consumed;
+mod registry;
+mod reload;
+mod validation;
 
+use std::collections::HashSet;
 use std::fmt::{self, Debug};
-use std::marker::PhantomData;
 use std::ops::Deref;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 
 use crate::actions::ActionKind;
 use crate::bindings::Bindings;
+use crate::buttons::Input;
 use crate::state::InputState;
 use crate::Event;
 
 use self::bindings_cache::BindingsCache;
+use self::capture::Capture;
+use self::consumed::Consumed;
+use self::registry::RegisteredAction;
+
+pub use self::capture::{AxisCapture, CaptureOptions, CaptureResult};
+pub use self::registry::{ActionInfo, ActionSetInfo};
+pub use self::reload::{BindingsWatcher, ReloadResult};
+pub use self::validation::{ValidationIssue, ValidationReport};
 
 /// The main entrypoint for using Keybee. [`ActionSet`]s are created from a
 /// `Session`, which can create [`Action`]s.
@@ -25,6 +40,9 @@ struct SessionInner {
     input: RwLock<InputState>,
     bindings: RwLock<Bindings>,
     bindings_cache: RwLock<BindingsCache>,
+    action_sets: RwLock<Vec<Weak<ActionSetState>>>,
+    actions: RwLock<Vec<Weak<dyn RegisteredAction>>>,
+    capture: Mutex<Option<Capture>>,
 }
 
 impl Session {
@@ -34,6 +52,9 @@ impl Session {
             input: RwLock::new(InputState::new()),
             bindings: RwLock::new(Bindings::new()),
             bindings_cache: RwLock::new(BindingsCache::new()),
+            action_sets: RwLock::new(Vec::new()),
+            actions: RwLock::new(Vec::new()),
+            capture: Mutex::new(None),
         });
 
         Self { inner }
@@ -42,37 +63,74 @@ impl Session {
     /// Create a new action set with the given name.
     #[must_use]
     pub fn create_action_set(&self, name: &str) -> ActionSet {
+        let state = Arc::new(ActionSetState {
+            name: name.to_owned(),
+            enabled: AtomicBool::new(true),
+            priority: AtomicI32::new(0),
+            mode: RwLock::new(ActionSetMode::Consume),
+            gated: Mutex::new(HashSet::new()),
+            canceled: Mutex::new(None),
+        });
+
+        let mut action_sets = self.inner.action_sets.write();
+        action_sets.retain(|set| set.strong_count() > 0);
+        action_sets.push(Arc::downgrade(&state));
+
         ActionSet {
             session: self.inner.clone(),
-            enabled: Arc::new(AtomicBool::new(true)),
-            name: name.to_owned(),
+            state,
         }
     }
 
     /// Clear all bindings in the session.
     pub fn clear_bindings(&self) {
+        let mut input = self.inner.input.write();
         let mut bindings = self.inner.bindings.write();
         let mut bindings_cache = self.inner.bindings_cache.write();
 
         bindings.clear();
         bindings_cache.clear();
+        input.watch_bindings(&[]);
     }
 
     /// Apply the given bindings to the session, merging them with the existing
     /// set of bindings.
     pub fn use_bindings(&self, new: Bindings) {
-        let mut bindings = self.inner.bindings.write();
-        let mut bindings_cache = self.inner.bindings_cache.write();
+        self.inner.update_bindings(|bindings| bindings.merge(new));
+    }
 
-        bindings.merge(new);
-        bindings_cache.clear();
+    /// Replace every binding in the session with the given bindings in one
+    /// step, so actions never see a mix of the old and new bindings.
+    pub fn replace_bindings(&self, new: Bindings) {
+        self.inner.update_bindings(|bindings| *bindings = new);
+    }
 
-        for (set_name, action_set) in &bindings.action_sets {
-            for (action_name, action_bindings) in &action_set.actions {
-                let full_name = format!("{}/{}", set_name, action_name);
-                bindings_cache.insert(full_name, action_bindings.clone());
-            }
-        }
+    /// Returns the session's current bindings.
+    pub fn bindings(&self) -> impl Deref<Target = Bindings> + '_ {
+        self.inner.bindings.read()
+    }
+
+    /// Check the given bindings against the actions and action sets created
+    /// from this session, reporting anything that won't work as expected.
+    pub fn validate_bindings(&self, bindings: &Bindings) -> ValidationReport {
+        let actions: Vec<_> = self
+            .inner
+            .actions
+            .read()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+
+        let set_names: Vec<_> = self.action_sets().map(|set| set.name).collect();
+
+        validation::validate(bindings, &set_names, &actions)
+    }
+
+    /// Check the session's current bindings against its actions and action
+    /// sets. See [`Session::validate_bindings`].
+    pub fn validate(&self) -> ValidationReport {
+        let bindings = self.inner.bindings.read();
+        self.validate_bindings(&bindings)
     }
 
     /// Sets the offset of the game viewport, used for reporting the cursor's
@@ -94,50 +152,326 @@ impl Session {
         E: TryInto<Event>,
     {
         if let Ok(event) = event.try_into() {
+            let pressed = match &event {
+                Event::ButtonPressed(button) => Some(Input::from(*button)),
+                _ => None,
+            };
+
             let mut input = self.inner.input.write();
             input.handle_event(event);
+            self.inner.lift_gates(&input, pressed);
+
+            let mut capture = self.inner.capture.lock();
+            if let Some(capture) = capture.as_mut() {
+                if capture.handle_event(&event, &input) {
+                    self.inner.gate_active(&input);
+                }
+            }
         }
     }
 
+    /// Start listening for the next button press or axis movement so that it
+    /// can be turned into a [`Binding`](crate::Binding), like for a "press a key
+    /// to rebind" screen.
+    ///
+    /// Every action is suspended until the capture is finished and collected
+    /// with [`Session::poll_capture`], or stopped with
+    /// [`Session::cancel_capture`]. Starting a new capture replaces any capture
+    /// in progress.
+    pub fn begin_capture(&self, options: CaptureOptions) {
+        let input = self.inner.input.read();
+        *self.inner.capture.lock() = Some(Capture::new(options, &input));
+    }
+
+    /// Returns the result of the current capture once it has finished, ending
+    /// it and resuming actions. Returns `None` while still listening.
+    ///
+    /// Any inputs held when the capture finishes are ignored by action sets
+    /// until they're released, so the captured button doesn't also trigger an
+    /// action.
+    pub fn poll_capture(&self) -> Option<CaptureResult> {
+        let mut capture = self.inner.capture.lock();
+        let result = capture.as_ref()?.result()?;
+        *capture = None;
+        Some(result)
+    }
+
+    /// Stop the current capture without a result, resuming actions.
+    pub fn cancel_capture(&self) {
+        *self.inner.capture.lock() = None;
+    }
+
+    /// Tells whether a capture started with [`Session::begin_capture`] hasn't
+    /// been collected or canceled yet.
+    pub fn is_capturing(&self) -> bool {
+        self.inner.capture.lock().is_some()
+    }
+
+    /// Indicate to Keybee that a game update is about to run, and how much time
+    /// has passed since the previous one. This advances time-based inputs like
+    /// ramped axes.
+    ///
+    /// Call this after handling the update's events and before reading actions.
+    pub fn begin_update(&mut self, delta: Duration) {
+        let mut input = self.inner.input.write();
+        input.begin_update(delta);
+    }
+
     /// Indicate to Keybee that a game update has just run. This resets any
     /// edge-triggered inputs like buttons or mouse motion.
     pub fn end_update(&mut self) {
         let mut input = self.inner.input.write();
         input.end_update();
+        self.inner.lift_gates(&input, None);
     }
 
     pub fn state(&self) -> impl Deref<Target = InputState> + '_ {
         self.inner.input.read()
     }
+
+    /// Returns information about every action set created from this session
+    /// that's still alive, in the order they were created.
+    pub fn action_sets(&self) -> impl Iterator<Item = ActionSetInfo> {
+        let action_sets: Vec<_> = self
+            .inner
+            .action_sets
+            .read()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+
+        action_sets.into_iter().map(|set| ActionSetInfo::new(&set))
+    }
+
+    /// Returns information about every action created from this session that's
+    /// still alive, in the order they were created.
+    pub fn actions(&self) -> impl Iterator<Item = ActionInfo> {
+        let actions: Vec<_> = self
+            .inner
+            .actions
+            .read()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+
+        actions.into_iter().map(|action| action.info())
+    }
+
+    /// Returns information about the action with the given full name, like
+    /// `gameplay/jump`.
+    pub fn action(&self, name: &str) -> Option<ActionInfo> {
+        self.actions().find(|action| action.name == name)
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionInner {
+    /// Change the session's bindings and rebuild everything derived from them
+    /// while holding every lock involved.
+    fn update_bindings<F>(&self, update: F)
+    where
+        F: FnOnce(&mut Bindings),
+    {
+        let mut input = self.input.write();
+        let mut bindings = self.bindings.write();
+        let mut bindings_cache = self.bindings_cache.write();
+
+        update(&mut bindings);
+        bindings_cache.clear();
+
+        for (set_name, action_set) in &bindings.action_sets {
+            for (action_name, action_bindings) in &action_set.actions {
+                bindings_cache.insert(set_name, action_name, action_bindings.clone());
+            }
+        }
+
+        input.watch_bindings(
+            bindings
+                .action_sets
+                .values()
+                .flat_map(|action_set| action_set.actions.values())
+                .flatten(),
+        );
+    }
+
+    /// Finds the inputs that higher priority action sets are hiding from the
+    /// given action set, writing them into `consumed`.
+    fn consumed(
+        &self,
+        set: &ActionSetState,
+        bindings_cache: &BindingsCache,
+        consumed: &mut Consumed,
+    ) {
+        let priority = set.priority();
+        consumed.clear();
+
+        for other in self.action_sets.read().iter().filter_map(Weak::upgrade) {
+            if !other.enabled() || other.priority() <= priority {
+                continue;
+            }
+
+            match other.mode() {
+                ActionSetMode::Consume => {
+                    if let Some(inputs) = bindings_cache.set_inputs(&other.name) {
+                        consumed.extend(inputs);
+                    }
+                }
+                ActionSetMode::Passthrough => {}
+                ActionSetMode::Blocking => {
+                    consumed.consume_all();
+                    return;
+                }
+            }
+        }
+
+        consumed.extend(set.gated.lock().iter());
+    }
+
+    /// Stop ignoring inputs that action sets were waiting on once they're
+    /// released or pressed again.
+    fn lift_gates(&self, input: &InputState, pressed: Option<Input>) {
+        for set in self.action_sets.read().iter().filter_map(Weak::upgrade) {
+            let mut gated = set.gated.lock();
+
+            if !gated.is_empty() {
+                gated.retain(|&gated| Some(gated) != pressed && input.is_input_active(gated));
+            }
+        }
+    }
+
+    /// Make every action set ignore the inputs that are currently active until
+    /// they're released or pressed again.
+    fn gate_active(&self, input: &InputState) {
+        let active = input.active_inputs();
+
+        for set in self.action_sets.read().iter().filter_map(Weak::upgrade) {
+            set.gated.lock().extend(active.iter().copied());
+        }
+    }
+
+    /// Finds the inputs bound in the given action set that are currently
+    /// active.
+    fn active_inputs(&self, set: &ActionSetState) -> HashSet<Input> {
+        let input = self.input.read();
+        let bindings_cache = self.bindings_cache.read();
+
+        bindings_cache
+            .set_inputs(&set.name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&i| input.is_input_active(i))
+            .collect()
+    }
+}
+
+/// Controls how an [`ActionSet`] shares inputs with action sets that have a
+/// lower priority.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ActionSetMode {
+    /// Inputs bound to this set's actions are hidden from lower priority sets
+    /// while this set is enabled.
+    #[default]
+    Consume,
+
+    /// Lower priority sets can use every input, even ones bound in this set.
+    Passthrough,
+
+    /// Lower priority sets can't use any inputs while this set is enabled.
+    Blocking,
+}
+
+struct ActionSetState {
+    name: String,
+    enabled: AtomicBool,
+    priority: AtomicI32,
+    mode: RwLock<ActionSetMode>,
+    gated: Mutex<HashSet<Input>>,
+    canceled: Mutex<Option<Cancellation>>,
+}
+
+/// Records which inputs were active when an action set was disabled.
+struct Cancellation {
+    update: u64,
+    inputs: HashSet<Input>,
+}
+
+impl ActionSetState {
+    fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority.load(Ordering::SeqCst)
+    }
+
+    fn mode(&self) -> ActionSetMode {
+        *self.mode.read()
+    }
 }
 
 /// Defines a group of actions that a player can perform.
 ///
+/// Action sets are stacked by priority. While an action set is enabled, action
+/// sets with a lower priority can't use the inputs it consumes, as described by
+/// its [`ActionSetMode`]. Action sets with the same priority never hide inputs
+/// from each other.
+///
 /// Created with [`Session::create_action_set`].
 pub struct ActionSet {
     session: Arc<SessionInner>,
-    enabled: Arc<AtomicBool>,
-    name: String,
+    state: Arc<ActionSetState>,
 }
 
 impl ActionSet {
     /// Create a new action that can be activated by the player.
     #[must_use]
-    pub fn create_action<K: ActionKind>(&self, name: &str, action: K) -> Action<K> {
-        let full_name = format!("{}/{}", self.name, name);
+    pub fn create_action<K>(&self, name: &str, action: K) -> Action<K>
+    where
+        K: ActionKind + Send + Sync + 'static,
+        K::Output: Send,
+    {
+        let full_name = format!("{}/{}", self.state.name, name);
 
-        Action {
+        let inner = Arc::new(ActionInner {
             session: self.session.clone(),
-            set_enabled: Arc::clone(&self.enabled),
+            set: Arc::clone(&self.state),
+            enabled: AtomicBool::new(true),
+            canceled: Mutex::new(None),
+            overridden: Mutex::new(None),
+            last_value: Mutex::new(None),
             storage: RwLock::new(action),
+            consumed: Mutex::new(Consumed::new()),
             full_name,
-            _phantom: PhantomData,
-        }
+        });
+
+        let registered: Arc<dyn RegisteredAction> = inner.clone();
+        let mut actions = self.session.actions.write();
+        actions.retain(|action| action.strong_count() > 0);
+        actions.push(Arc::downgrade(&registered));
+
+        Action { inner }
     }
 
     /// Enable or disable all actions within this action set.
+    ///
+    /// Disabling an action set cancels any of its actions that were in use, as
+    /// reported by [`Action::canceled`].
     pub fn set_enabled(&self, value: bool) {
-        self.enabled.store(value, Ordering::SeqCst);
+        if value {
+            self.state.gated.lock().clear();
+        } else if self.enabled() {
+            let update = self.session.input.read().update_count();
+            let inputs = self.session.active_inputs(&self.state);
+            *self.state.canceled.lock() = Some(Cancellation { update, inputs });
+        }
+
+        self.state.enabled.store(value, Ordering::SeqCst);
     }
 
     /// Enable all actions within this action set.
@@ -145,6 +479,17 @@ impl ActionSet {
         self.set_enabled(true);
     }
 
+    /// Enable all actions within this action set, but ignore any bound inputs
+    /// that are currently active until they're released or pressed again.
+    ///
+    /// This stops a button held to close a menu from immediately triggering an
+    /// action in the set that the menu returns to.
+    pub fn enable_after_release(&self) {
+        let active = self.session.active_inputs(&self.state);
+        self.set_enabled(true);
+        *self.state.gated.lock() = active;
+    }
+
     /// Disable all actions within this action set.
     pub fn disable(&self) {
         self.set_enabled(false);
@@ -152,33 +497,218 @@ impl ActionSet {
 
     /// Tells whether the action set is currently enabled.
     pub fn enabled(&self) -> bool {
-        self.enabled.load(Ordering::SeqCst)
+        self.state.enabled()
+    }
+
+    /// Set the priority of the action set. Action sets with a higher priority
+    /// are stacked above ones with a lower priority. The default priority is 0.
+    pub fn set_priority(&self, priority: i32) {
+        self.state.priority.store(priority, Ordering::SeqCst);
+    }
+
+    /// Returns the priority of the action set.
+    pub fn priority(&self) -> i32 {
+        self.state.priority()
+    }
+
+    /// Set how the action set shares inputs with lower priority action sets.
+    pub fn set_mode(&self, mode: ActionSetMode) {
+        *self.state.mode.write() = mode;
+    }
+
+    /// Returns how the action set shares inputs with lower priority action
+    /// sets.
+    pub fn mode(&self) -> ActionSetMode {
+        self.state.mode()
+    }
+
+    /// Enable this action set and move it above every other action set in the
+    /// session.
+    pub fn push(&self) {
+        let highest = self
+            .session
+            .action_sets
+            .read()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|other| !Arc::ptr_eq(other, &self.state))
+            .map(|other| other.priority())
+            .max();
+
+        if let Some(highest) = highest {
+            self.set_priority(self.priority().max(highest.saturating_add(1)));
+        }
+
+        self.enable();
     }
 
     /// Returns the name of the action set given when it was created.
     pub fn name(&self) -> &str {
-        &self.name
+        &self.state.name
     }
 }
 
 /// Defines something that a player can do.
 ///
 /// Created with [`ActionSet::create_action`].
-pub struct Action<K> {
+pub struct Action<K: ActionKind> {
+    inner: Arc<ActionInner<K>>,
+}
+
+struct ActionInner<K: ActionKind> {
     session: Arc<SessionInner>,
-    set_enabled: Arc<AtomicBool>,
+    set: Arc<ActionSetState>,
+    enabled: AtomicBool,
+    canceled: Mutex<Option<Cancellation>>,
+    overridden: Mutex<Option<Override<K::Output>>>,
+    last_value: Mutex<Option<K::Output>>,
     storage: RwLock<K>,
+    consumed: Mutex<Consumed>,
     full_name: String,
-    _phantom: PhantomData<*const K>,
+}
+
+/// A value that replaces an action's output, optionally until a given update.
+struct Override<T> {
+    value: T,
+    until: Option<u64>,
 }
 
 impl<K: ActionKind> Action<K> {
     /// Get the current state of the action.
+    ///
+    /// If the action is overridden, the override value is returned even if the
+    /// action or its action set is disabled.
     #[must_use]
     pub fn get(&self) -> K::Output {
+        self.inner.get()
+    }
+
+    /// Enable or disable this action, independently of its action set.
+    ///
+    /// Disabling an action that's in use cancels it, as reported by
+    /// [`Action::canceled`].
+    pub fn set_enabled(&self, value: bool) {
+        if !value && self.enabled() {
+            let input = self.inner.session.input.read();
+            let bindings_cache = self.inner.session.bindings_cache.read();
+
+            let inputs = bindings_cache
+                .get(&self.inner.full_name)
+                .unwrap_or(&[])
+                .iter()
+                .flat_map(|binding| binding.inputs())
+                .filter(|&i| input.is_input_active(i))
+                .collect();
+
+            *self.inner.canceled.lock() = Some(Cancellation {
+                update: input.update_count(),
+                inputs,
+            });
+        }
+
+        self.inner.enabled.store(value, Ordering::SeqCst);
+    }
+
+    /// Enable this action.
+    pub fn enable(&self) {
+        self.set_enabled(true);
+    }
+
+    /// Disable this action.
+    pub fn disable(&self) {
+        self.set_enabled(false);
+    }
+
+    /// Tells whether this action is enabled. An enabled action still does
+    /// nothing while its action set is disabled.
+    pub fn enabled(&self) -> bool {
+        self.inner.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Tells whether the action was canceled during the current update because
+    /// it or its action set was disabled while one of its bound inputs was
+    /// active.
+    #[must_use]
+    pub fn canceled(&self) -> bool {
+        let input = self.inner.session.input.read();
+        let bindings_cache = self.inner.session.bindings_cache.read();
+        let update = input.update_count();
+
+        let inputs: Vec<_> = bindings_cache
+            .get(&self.inner.full_name)
+            .unwrap_or(&[])
+            .iter()
+            .flat_map(|binding| binding.inputs())
+            .collect();
+
+        [
+            &*self.inner.set.canceled.lock(),
+            &*self.inner.canceled.lock(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|cancellation| cancellation.update == update)
+        .any(|cancellation| inputs.iter().any(|i| cancellation.inputs.contains(i)))
+    }
+
+    /// Make the action produce the given value instead of reading its bindings,
+    /// until [`Action::clear_override`] is called.
+    pub fn set_override(&self, value: K::Output) {
+        *self.inner.overridden.lock() = Some(Override { value, until: None });
+    }
+
+    /// Make the action produce the given value instead of reading its bindings
+    /// for the given number of updates, including the current one.
+    pub fn set_override_for(&self, value: K::Output, updates: u64) {
+        let update = self.inner.session.input.read().update_count();
+        let until = update.saturating_add(updates);
+
+        *self.inner.overridden.lock() = Some(Override {
+            value,
+            until: Some(until),
+        });
+    }
+
+    /// Remove any override from the action, letting it read its bindings again.
+    pub fn clear_override(&self) {
+        *self.inner.overridden.lock() = None;
+    }
+
+    /// Tells whether the action's value is currently overridden.
+    pub fn is_overridden(&self) -> bool {
+        let update = self.inner.session.input.read().update_count();
+        self.inner.override_value(update).is_some()
+    }
+
+    /// Returns the full name of the action, including the action set it's part
+    /// of.
+    ///
+    /// Action names are of the form `{set}/{action}`. An `Action` named "jump"
+    /// created in an [`ActionSet`] named "gameplay" will have the name
+    /// `gameplay/jump`.
+    pub fn name(&self) -> &str {
+        &self.inner.full_name
+    }
+}
+
+impl<K: ActionKind> ActionInner<K> {
+    fn get(&self) -> K::Output {
+        let value = self.read();
+        *self.last_value.lock() = Some(value.clone());
+        value
+    }
+
+    fn read(&self) -> K::Output {
         let input = self.session.input.read();
+
+        if let Some(value) = self.override_value(input.update_count()) {
+            return value;
+        }
+
         let bindings_cache = self.session.bindings_cache.read();
-        let enabled = self.set_enabled.load(Ordering::SeqCst);
+        let enabled = self.set.enabled()
+            && self.enabled.load(Ordering::SeqCst)
+            && self.session.capture.lock().is_none();
 
         let bindings = if enabled {
             bindings_cache.get(&self.full_name).unwrap_or(&[])
@@ -186,32 +716,42 @@ impl<K: ActionKind> Action<K> {
             &[]
         };
 
+        let mut consumed = self.consumed.lock();
+        self.session
+            .consumed(&self.set, &bindings_cache, &mut consumed);
         let mut storage = self.storage.write();
 
         let inputs: Vec<_> = bindings
             .iter()
-            .filter_map(|binding| storage.get(&input, binding))
+            .map(|binding| {
+                if consumed.hides(binding) {
+                    None
+                } else {
+                    storage.get(&input, binding)
+                }
+            })
             .collect();
 
-        storage.reduce(&inputs)
+        storage.reduce(&input, &inputs)
     }
 
-    /// Returns the full name of the action, including the action set it's part
-    /// of.
-    ///
-    /// Action names are of the form `{set}/{action}`. An `Action` named "jump"
-    /// created in an [`ActionSet`] named "gameplay" will have the name
-    /// `gameplay/jump`.
-    pub fn name(&self) -> &str {
-        &self.full_name
+    fn override_value(&self, update: u64) -> Option<K::Output> {
+        let mut overridden = self.overridden.lock();
+
+        match &*overridden {
+            Some(Override {
+                until: Some(until), ..
+            }) if update >= *until => {
+                *overridden = None;
+                None
+            }
+            Some(Override { value, .. }) => Some(value.clone()),
+            None => None,
+        }
     }
 }
 
-impl<K> Debug for Action<K>
-where
-    K: ActionKind,
-    K::Output: Debug,
-{
+impl<K: ActionKind> Debug for Action<K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -224,7 +764,11 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::{ActionSetBindings, Button, EventAction, KeyboardKey};
+    use crate::{
+        ActionSetBindings, Axis1d, Axis1dAction, Axis1dBinding, AxisDirection, Binding,
+        BindingShape, BoolAction, Button, EventAction, GamepadAxis1d, KeyboardKey,
+        ThresholdBinding, ThresholdSource,
+    };
 
     use super::*;
 
@@ -253,4 +797,229 @@ mod test {
         session.handle_event(Event::ButtonPressed(KeyboardKey::Space.into()));
         assert!(jump.get());
     }
+
+    #[test]
+    fn threshold_action() {
+        let mut session = Session::new();
+        let set = session.create_action_set("gameplay");
+        let fire = set.create_action("fire", EventAction);
+        let firing = set.create_action("firing", BoolAction);
+
+        let trigger = Axis1d::Gamepad(GamepadAxis1d::RightTrigger);
+        let threshold: Binding = ThresholdBinding {
+            source: ThresholdSource::Axis1d(trigger, AxisDirection::Positive),
+            press: 0.5,
+            release: 0.25,
+        }
+        .into();
+
+        let mut bindings = Bindings::new();
+        let mut gameplay = ActionSetBindings::new();
+        gameplay.insert("fire", vec![threshold]);
+        gameplay.insert("firing", vec![threshold]);
+        bindings.insert("gameplay", gameplay);
+
+        session.use_bindings(bindings);
+
+        session.handle_event(Event::Axis1dChanged(trigger, 0.8));
+        assert!(fire.get());
+        assert!(firing.get());
+
+        session.end_update();
+        assert!(!fire.get());
+        assert!(firing.get());
+
+        session.handle_event(Event::Axis1dChanged(trigger, 0.4));
+        assert!(firing.get());
+
+        session.handle_event(Event::Axis1dChanged(trigger, 0.1));
+        assert!(!firing.get());
+    }
+
+    fn gameplay_and_menu() -> (Session, ActionSet, ActionSet) {
+        let session = Session::new();
+        let gameplay = session.create_action_set("gameplay");
+        let menu = session.create_action_set("menu");
+
+        let mut bindings = Bindings::new();
+        let mut gameplay_bindings = ActionSetBindings::new();
+        gameplay_bindings.insert("jump", vec![KeyboardKey::Space.into()]);
+        gameplay_bindings.insert("hold_jump", vec![KeyboardKey::Space.into()]);
+        gameplay_bindings.insert(
+            "move",
+            vec![Binding::Axis1d(Axis1dBinding::Buttons {
+                neg: KeyboardKey::A.into(),
+                pos: KeyboardKey::D.into(),
+                sensitivity: 1.0,
+                ramp: None,
+            })],
+        );
+        bindings.insert("gameplay", gameplay_bindings);
+
+        let mut menu_bindings = ActionSetBindings::new();
+        menu_bindings.insert("confirm", vec![KeyboardKey::Space.into()]);
+        bindings.insert("menu", menu_bindings);
+
+        session.use_bindings(bindings);
+
+        (session, gameplay, menu)
+    }
+
+    #[test]
+    fn consume_inputs() {
+        let (mut session, gameplay, menu) = gameplay_and_menu();
+        let jump = gameplay.create_action("jump", BoolAction);
+        let movement = gameplay.create_action("move", Axis1dAction);
+        let confirm = menu.create_action("confirm", BoolAction);
+
+        session.handle_event(Event::ButtonPressed(KeyboardKey::Space.into()));
+        session.handle_event(Event::ButtonPressed(KeyboardKey::D.into()));
+        assert!(jump.get());
+        assert!(confirm.get());
+
+        menu.push();
+        assert!(menu.priority() > gameplay.priority());
+        assert!(!jump.get());
+        assert!(confirm.get());
+        assert_eq!(movement.get(), 1.0);
+
+        menu.set_mode(ActionSetMode::Passthrough);
+        assert!(jump.get());
+
+        menu.set_mode(ActionSetMode::Blocking);
+        assert!(!jump.get());
+        assert_eq!(movement.get(), 0.0);
+
+        menu.disable();
+        assert!(jump.get());
+        assert_eq!(movement.get(), 1.0);
+    }
+
+    #[test]
+    fn enable_after_release() {
+        let (mut session, gameplay, menu) = gameplay_and_menu();
+        let jump = gameplay.create_action("jump", EventAction);
+        let held = gameplay.create_action("hold_jump", BoolAction);
+        let confirm = menu.create_action("confirm", EventAction);
+
+        gameplay.disable();
+        session.handle_event(Event::ButtonPressed(KeyboardKey::Space.into()));
+        assert!(confirm.get());
+
+        menu.disable();
+        gameplay.enable_after_release();
+        assert!(!jump.get());
+        assert!(!held.get());
+
+        session.end_update();
+        assert!(!held.get());
+
+        session.handle_event(Event::ButtonReleased(KeyboardKey::Space.into()));
+        session.end_update();
+        session.handle_event(Event::ButtonPressed(KeyboardKey::Space.into()));
+        assert!(jump.get());
+        assert!(held.get());
+    }
+
+    #[test]
+    fn cancel_on_disable() {
+        let (mut session, gameplay, _menu) = gameplay_and_menu();
+        let jump = gameplay.create_action("jump", BoolAction);
+        let movement = gameplay.create_action("move", Axis1dAction);
+
+        session.handle_event(Event::ButtonPressed(KeyboardKey::Space.into()));
+        assert!(jump.get());
+
+        gameplay.disable();
+        assert!(!jump.get());
+        assert!(jump.canceled());
+        assert!(!movement.canceled());
+
+        session.end_update();
+        assert!(!jump.canceled());
+    }
+
+    #[test]
+    fn disable_action() {
+        let (mut session, gameplay, _menu) = gameplay_and_menu();
+        let jump = gameplay.create_action("jump", BoolAction);
+
+        session.handle_event(Event::ButtonPressed(KeyboardKey::Space.into()));
+        assert!(jump.get());
+
+        jump.disable();
+        assert!(!jump.get());
+        assert!(jump.canceled());
+
+        session.end_update();
+        jump.enable();
+        assert!(jump.get());
+    }
+
+    #[test]
+    fn override_action() {
+        let (mut session, gameplay, _menu) = gameplay_and_menu();
+        let movement = gameplay.create_action("move", Axis1dAction);
+
+        movement.set_override(-1.0);
+        gameplay.disable();
+        assert_eq!(movement.get(), -1.0);
+
+        gameplay.enable();
+        movement.clear_override();
+        assert_eq!(movement.get(), 0.0);
+
+        movement.set_override_for(0.5, 2);
+        assert_eq!(movement.get(), 0.5);
+        session.end_update();
+        assert_eq!(movement.get(), 0.5);
+        assert!(movement.is_overridden());
+        session.end_update();
+        assert_eq!(movement.get(), 0.0);
+        assert!(!movement.is_overridden());
+    }
+
+    #[test]
+    fn registry() {
+        let (mut session, gameplay, menu) = gameplay_and_menu();
+        let jump = gameplay.create_action("jump", BoolAction);
+        let _movement = gameplay.create_action("move", Axis1dAction);
+
+        {
+            let _confirm = menu.create_action("confirm", EventAction);
+            assert_eq!(session.actions().count(), 3);
+        }
+
+        let names: Vec<_> = session.actions().map(|action| action.name).collect();
+        assert_eq!(names, ["gameplay/jump", "gameplay/move"]);
+
+        session.handle_event(Event::ButtonPressed(KeyboardKey::Space.into()));
+        jump.disable();
+
+        let info = session.action("gameplay/jump").unwrap();
+        assert_eq!(info.action_set, "gameplay");
+        assert_eq!(info.kind, std::any::type_name::<BoolAction>());
+        assert_eq!(
+            info.accepts,
+            [
+                BindingShape::Button,
+                BindingShape::Threshold,
+                BindingShape::Chord
+            ]
+        );
+        assert!(!info.enabled);
+        assert_eq!(info.bindings, [Binding::from(KeyboardKey::Space)]);
+        assert_eq!(info.value, None);
+
+        assert!(!jump.get());
+        let info = session.action("gameplay/jump").unwrap();
+        assert_eq!(info.value.as_deref(), Some("false"));
+
+        menu.set_priority(5);
+        let sets: Vec<_> = session
+            .action_sets()
+            .map(|set| (set.name, set.priority))
+            .collect();
+        assert_eq!(sets, [("gameplay".to_owned(), 0), ("menu".to_owned(), 5)]);
+    }
 }
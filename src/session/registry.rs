@@ -0,0 +1,115 @@
+use std::sync::atomic::Ordering;
+
+use crate::actions::ActionKind;
+use crate::bindings::{Binding, BindingShape};
+
+use super::{ActionInner, ActionSetMode, ActionSetState};
+
+/// Lets a session describe its actions without knowing their kinds.
+pub(super) trait RegisteredAction: Send + Sync {
+    fn info(&self) -> ActionInfo;
+    fn full_name(&self) -> &str;
+    fn set_name(&self) -> &str;
+    fn accepts(&self, shape: BindingShape) -> bool;
+}
+
+impl<K> RegisteredAction for ActionInner<K>
+where
+    K: ActionKind + Send + Sync,
+    K::Output: Send,
+{
+    fn info(&self) -> ActionInfo {
+        let accepts = BindingShape::ALL
+            .into_iter()
+            .filter(|&shape| RegisteredAction::accepts(self, shape))
+            .collect();
+
+        let bindings = self
+            .session
+            .bindings_cache
+            .read()
+            .get(&self.full_name)
+            .unwrap_or(&[])
+            .to_vec();
+
+        ActionInfo {
+            name: self.full_name.clone(),
+            action_set: self.set.name.clone(),
+            kind: std::any::type_name::<K>(),
+            accepts,
+            enabled: self.enabled.load(Ordering::SeqCst),
+            bindings,
+            value: self
+                .last_value
+                .lock()
+                .as_ref()
+                .map(|value| format!("{:?}", value)),
+        }
+    }
+
+    fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    fn set_name(&self) -> &str {
+        &self.set.name
+    }
+
+    fn accepts(&self, shape: BindingShape) -> bool {
+        self.storage.read().accepts(shape)
+    }
+}
+
+/// Describes an [`Action`](super::Action) created from a
+/// [`Session`](super::Session).
+///
+/// Returned by [`Session::actions`](super::Session::actions).
+#[derive(Debug, Clone)]
+pub struct ActionInfo {
+    /// The full name of the action, like `gameplay/jump`.
+    pub name: String,
+
+    /// The name of the action set that the action belongs to.
+    pub action_set: String,
+
+    /// The type name of the action's [`ActionKind`].
+    pub kind: &'static str,
+
+    /// The binding shapes that the action can read.
+    pub accepts: Vec<BindingShape>,
+
+    /// Whether the action itself is enabled. This doesn't consider whether its
+    /// action set is enabled.
+    pub enabled: bool,
+
+    /// The bindings currently assigned to the action.
+    pub bindings: Vec<Binding>,
+
+    /// The value from the last time the action was read, formatted with
+    /// [`Debug`], or `None` if it hasn't been read yet. Getting this info
+    /// doesn't read the action, so actions that keep state aren't affected.
+    pub value: Option<String>,
+}
+
+/// Describes an [`ActionSet`](super::ActionSet) created from a
+/// [`Session`](super::Session).
+///
+/// Returned by [`Session::action_sets`](super::Session::action_sets).
+#[derive(Debug, Clone)]
+pub struct ActionSetInfo {
+    pub name: String,
+    pub enabled: bool,
+    pub priority: i32,
+    pub mode: ActionSetMode,
+}
+
+impl ActionSetInfo {
+    pub(super) fn new(set: &ActionSetState) -> Self {
+        Self {
+            name: set.name.clone(),
+            enabled: set.enabled(),
+            priority: set.priority(),
+            mode: set.mode(),
+        }
+    }
+}
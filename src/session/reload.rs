@@ -0,0 +1,215 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::bindings::Bindings;
+
+use super::{Session, ValidationReport};
+
+/// Reloads bindings from a file whenever it changes, for tweaking bindings
+/// while the game is running.
+///
+/// The watcher doesn't use any platform file notification APIs. Instead, call
+/// [`BindingsWatcher::poll`] every so often, like once a second, and it will
+/// read the file and check whether its contents changed. Comparing contents
+/// instead of modification times catches edits made within the timestamp
+/// granularity of the file system.
+///
+/// Files are parsed with the function given to [`BindingsWatcher::new`], so
+/// any format can be used:
+///
+/// ```ignore
+/// let mut watcher = BindingsWatcher::new("bindings.json", |text| {
+///     Ok(serde_json::from_str(text)?)
+/// });
+/// ```
+pub struct BindingsWatcher<F> {
+    path: PathBuf,
+    parse: F,
+    last: Option<FileStamp>,
+}
+
+/// What happened when a [`BindingsWatcher`] found a change.
+#[derive(Debug)]
+pub enum ReloadResult {
+    /// The file was parsed and its bindings replaced the session's bindings.
+    /// The report lists anything about the new bindings that won't work as
+    /// expected.
+    Reloaded(ValidationReport),
+
+    /// The file couldn't be read or parsed. The session keeps the bindings it
+    /// had before.
+    Failed(anyhow::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FileStamp {
+    Missing,
+    Present { hash: u64 },
+}
+
+impl<F> BindingsWatcher<F>
+where
+    F: Fn(&str) -> anyhow::Result<Bindings>,
+{
+    /// Watch the file at the given path. The file is loaded on the first call
+    /// to [`BindingsWatcher::poll`].
+    pub fn new<P: Into<PathBuf>>(path: P, parse: F) -> Self {
+        Self {
+            path: path.into(),
+            parse,
+            last: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Check whether the file changed since the last poll, and if it did,
+    /// reload it and replace the session's bindings with it.
+    ///
+    /// Returns `None` if the file hasn't changed. Errors are only reported
+    /// once per change to the file, so a broken file doesn't produce an error
+    /// on every poll.
+    pub fn poll(&mut self, session: &Session) -> Option<ReloadResult> {
+        let text = fs::read_to_string(&self.path);
+        let stamp = match &text {
+            Ok(text) => {
+                let mut hasher = DefaultHasher::new();
+                text.hash(&mut hasher);
+                FileStamp::Present {
+                    hash: hasher.finish(),
+                }
+            }
+            Err(_) => FileStamp::Missing,
+        };
+
+        if self.last == Some(stamp) {
+            return None;
+        }
+
+        self.last = Some(stamp);
+        Some(self.apply(session, text))
+    }
+
+    /// Reload the file and replace the session's bindings with it, whether or
+    /// not it changed.
+    pub fn reload(&self, session: &Session) -> ReloadResult {
+        self.apply(session, fs::read_to_string(&self.path))
+    }
+
+    fn apply(&self, session: &Session, text: io::Result<String>) -> ReloadResult {
+        match self.load(text) {
+            Ok(bindings) => {
+                let report = session.validate_bindings(&bindings);
+                session.replace_bindings(bindings);
+                ReloadResult::Reloaded(report)
+            }
+            Err(err) => ReloadResult::Failed(err),
+        }
+    }
+
+    fn load(&self, text: io::Result<String>) -> anyhow::Result<Bindings> {
+        let text = text.with_context(|| format!("failed to read {}", self.path.display()))?;
+
+        (self.parse)(&text).with_context(|| format!("failed to parse {}", self.path.display()))
+    }
+}
+
+impl<F> fmt::Debug for BindingsWatcher<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BindingsWatcher")
+            .field("path", &self.path)
+            .field("last", &self.last)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{GamepadButton, KeyboardKey};
+
+    use super::*;
+
+    fn parse(text: &str) -> anyhow::Result<Bindings> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    fn jump(session: &Session) -> Vec<crate::Binding> {
+        session
+            .bindings()
+            .get("gameplay")
+            .and_then(|set| set.get("jump"))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn reload_on_change() {
+        let path = std::env::temp_dir().join(format!("keybee-reload-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let session = Session::new();
+        let _set = session.create_action_set("gameplay");
+        let mut watcher = BindingsWatcher::new(&path, parse);
+
+        let Some(ReloadResult::Failed(_)) = watcher.poll(&session) else {
+            panic!("missing file should fail to load");
+        };
+        assert!(watcher.poll(&session).is_none());
+
+        fs::write(&path, r#"{ "gameplay": { "jump": ["keyboard/space"] } }"#).unwrap();
+        let Some(ReloadResult::Reloaded(report)) = watcher.poll(&session) else {
+            panic!("file should have reloaded");
+        };
+        assert!(!report.is_ok());
+        assert_eq!(jump(&session), [KeyboardKey::Space.into()]);
+        assert!(watcher.poll(&session).is_none());
+
+        fs::write(
+            &path,
+            r#"{ "gameplay": { "jump": ["keyboard/nonsense"] } }"#,
+        )
+        .unwrap();
+        let Some(ReloadResult::Failed(err)) = watcher.poll(&session) else {
+            panic!("broken file should fail to load");
+        };
+        assert!(format!("{:#}", err).contains("failed to parse"));
+        assert_eq!(jump(&session), [KeyboardKey::Space.into()]);
+
+        fs::write(
+            &path,
+            r#"{ "gameplay": { "jump": ["gamepad/a", "keyboard/j"] } }"#,
+        )
+        .unwrap();
+        let Some(ReloadResult::Reloaded(_)) = watcher.poll(&session) else {
+            panic!("fixed file should have reloaded");
+        };
+        assert_eq!(
+            jump(&session),
+            [GamepadButton::A.into(), KeyboardKey::J.into()]
+        );
+
+        // Same length, and likely written within the same timestamp.
+        fs::write(
+            &path,
+            r#"{ "gameplay": { "jump": ["gamepad/a", "keyboard/k"] } }"#,
+        )
+        .unwrap();
+        let Some(ReloadResult::Reloaded(_)) = watcher.poll(&session) else {
+            panic!("same-length edit should have reloaded");
+        };
+        assert_eq!(
+            jump(&session),
+            [GamepadButton::A.into(), KeyboardKey::K.into()]
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::bindings::{Binding, BindingShape, Bindings};
+
+use super::registry::RegisteredAction;
+
+/// The result of checking bindings against a session's actions.
+///
+/// Returned by [`Session::validate_bindings`](super::Session::validate_bindings).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Everything that was found, sorted by action set and action name.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Tells whether no issues were found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single problem found while validating bindings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// Bindings were given for an action set that was never created.
+    UnknownActionSet { action_set: String },
+
+    /// Bindings were given for an action that was never created in a known
+    /// action set.
+    UnknownAction { action: String },
+
+    /// A binding has a shape that the action can't read, like a 2D axis bound
+    /// to a bool action.
+    UnsupportedShape {
+        action: String,
+        index: usize,
+        binding: Binding,
+        shape: BindingShape,
+    },
+
+    /// The same binding appears more than once for an action.
+    DuplicateBinding {
+        action: String,
+        index: usize,
+        binding: Binding,
+    },
+
+    /// A threshold binding releases above the point where it presses.
+    InvertedThreshold {
+        action: String,
+        index: usize,
+        binding: Binding,
+    },
+
+    /// An action was created but has no bindings.
+    UnboundAction { action: String },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownActionSet { action_set } => {
+                write!(f, "unknown action set '{}'", action_set)
+            }
+            Self::UnknownAction { action } => write!(f, "unknown action '{}'", action),
+            Self::UnsupportedShape {
+                action,
+                index,
+                shape,
+                ..
+            } => write!(
+                f,
+                "binding {} of '{}' is a {:?} binding, which the action does not accept",
+                index, action, shape
+            ),
+            Self::DuplicateBinding { action, index, .. } => {
+                write!(f, "binding {} of '{}' is a duplicate", index, action)
+            }
+            Self::InvertedThreshold { action, index, .. } => write!(
+                f,
+                "binding {} of '{}' releases above the point where it presses",
+                index, action
+            ),
+            Self::UnboundAction { action } => write!(f, "action '{}' has no bindings", action),
+        }
+    }
+}
+
+pub(super) fn validate(
+    bindings: &Bindings,
+    set_names: &[String],
+    actions: &[Arc<dyn RegisteredAction>],
+) -> ValidationReport {
+    let mut issues = Vec::new();
+
+    for (set_name, set_bindings) in &bindings.action_sets {
+        if !set_names.contains(set_name) {
+            issues.push(ValidationIssue::UnknownActionSet {
+                action_set: set_name.clone(),
+            });
+            continue;
+        }
+
+        for (action_name, action_bindings) in &set_bindings.actions {
+            let full_name = format!("{}/{}", set_name, action_name);
+            let registered: Vec<_> = actions
+                .iter()
+                .filter(|action| action.full_name() == full_name)
+                .collect();
+
+            if registered.is_empty() {
+                issues.push(ValidationIssue::UnknownAction { action: full_name });
+                continue;
+            }
+
+            for (index, binding) in action_bindings.iter().enumerate() {
+                let shape = binding.shape();
+
+                if !registered.iter().all(|action| action.accepts(shape)) {
+                    issues.push(ValidationIssue::UnsupportedShape {
+                        action: full_name.clone(),
+                        index,
+                        binding: *binding,
+                        shape,
+                    });
+                }
+
+                if let Binding::Threshold(threshold) = binding {
+                    if threshold.release > threshold.press {
+                        issues.push(ValidationIssue::InvertedThreshold {
+                            action: full_name.clone(),
+                            index,
+                            binding: *binding,
+                        });
+                    }
+                }
+
+                if action_bindings[..index].contains(binding) {
+                    issues.push(ValidationIssue::DuplicateBinding {
+                        action: full_name.clone(),
+                        index,
+                        binding: *binding,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut unbound = HashSet::new();
+    for action in actions {
+        let bound = bindings
+            .get(action.set_name())
+            .and_then(|set| set.get(action_name(action.full_name())))
+            .is_some_and(|bindings| !bindings.is_empty());
+
+        if !bound {
+            unbound.insert(action.full_name().to_owned());
+        }
+    }
+
+    let mut unbound: Vec<_> = unbound.into_iter().collect();
+    unbound.sort();
+    issues.extend(
+        unbound
+            .into_iter()
+            .map(|action| ValidationIssue::UnboundAction { action }),
+    );
+
+    ValidationReport { issues }
+}
+
+fn action_name(full_name: &str) -> &str {
+    full_name
+        .split_once('/')
+        .map_or(full_name, |(_set, action)| action)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ActionSetBindings, Axis1d, Axis2d, Axis2dBinding, AxisDirection, BoolAction, Button,
+        GamepadAxis1d, GamepadAxis2d, KeyboardKey, Session, ThresholdBinding, ThresholdSource,
+    };
+
+    use super::*;
+
+    #[test]
+    fn report_issues() {
+        let session = Session::new();
+        let gameplay = session.create_action_set("gameplay");
+        let _jump = gameplay.create_action("jump", BoolAction);
+        let _crouch = gameplay.create_action("crouch", BoolAction);
+
+        let space = Binding::from(Button::Keyboard(KeyboardKey::Space));
+        let stick = Binding::Axis2d(Axis2dBinding::Axis {
+            axis: Axis2d::Gamepad(GamepadAxis2d::LeftStick),
+            sensitivity: 1.0,
+        });
+
+        let inverted = Binding::Threshold(ThresholdBinding {
+            source: ThresholdSource::Axis1d(
+                Axis1d::Gamepad(GamepadAxis1d::RightTrigger),
+                AxisDirection::Positive,
+            ),
+            press: 0.25,
+            release: 0.5,
+        });
+
+        let mut gameplay_bindings = ActionSetBindings::new();
+        gameplay_bindings.insert("jump", vec![space, stick, space, inverted]);
+        gameplay_bindings.insert("fly", vec![space]);
+        let mut menu_bindings = ActionSetBindings::new();
+        menu_bindings.insert("confirm", vec![space]);
+
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", gameplay_bindings);
+        bindings.insert("menu", menu_bindings);
+
+        let report = session.validate_bindings(&bindings);
+        assert_eq!(
+            report.issues,
+            vec![
+                ValidationIssue::UnknownAction {
+                    action: "gameplay/fly".to_owned(),
+                },
+                ValidationIssue::UnsupportedShape {
+                    action: "gameplay/jump".to_owned(),
+                    index: 1,
+                    binding: stick,
+                    shape: BindingShape::Axis2d,
+                },
+                ValidationIssue::DuplicateBinding {
+                    action: "gameplay/jump".to_owned(),
+                    index: 2,
+                    binding: space,
+                },
+                ValidationIssue::InvertedThreshold {
+                    action: "gameplay/jump".to_owned(),
+                    index: 3,
+                    binding: inverted,
+                },
+                ValidationIssue::UnknownActionSet {
+                    action_set: "menu".to_owned(),
+                },
+                ValidationIssue::UnboundAction {
+                    action: "gameplay/crouch".to_owned(),
+                },
+            ]
+        );
+
+        let mut gameplay_bindings = ActionSetBindings::new();
+        gameplay_bindings.insert("jump", vec![space]);
+        gameplay_bindings.insert("crouch", vec![space]);
+
+        let mut bindings = Bindings::new();
+        bindings.insert("gameplay", gameplay_bindings);
+        session.use_bindings(bindings);
+        assert!(session.validate().is_ok());
+    }
+}
@@ -1,8 +1,17 @@
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
-use crate::buttons::{Axis1d, Axis2d, Button, MouseAxis1d};
+use crate::bindings::{
+    Axis1dBinding, Axis2dBinding, Axis2dDirection, Axis3dBinding, AxisDirection, Binding, Ramp,
+    ThresholdBinding, ThresholdSource,
+};
+use crate::buttons::{Axis1d, Axis2d, Button, Gamepad, Input, Mouse, MouseAxis1d};
 use crate::event::Event;
 
+/// How far an axis has to be pushed from center before
+/// [`InputState::is_input_active`] considers it active.
+pub const ACTIVE_AXIS_THRESHOLD: f32 = 0.25;
+
 /// Tracks all of the state for a Keybee session, like what buttons are down or
 /// where the cursor is.
 #[derive(Debug)]
@@ -10,12 +19,23 @@ pub struct InputState {
     buttons: HashMap<Button, ButtonState>,
     axes_1d: HashMap<Axis1d, f32>,
     axes_2d: HashMap<Axis2d, [f32; 2]>,
+    thresholds: Vec<(ThresholdBinding, ButtonState)>,
+    ramps: Vec<(RampedAxis, f32)>,
+    update_count: u64,
+    delta: Duration,
     mouse_motion: [f32; 2],
     mouse_wheel: [f32; 2],
     cursor_position: [f32; 2],
     viewport_position: [f32; 2],
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RampedAxis {
+    neg: Button,
+    pos: Button,
+    ramp: Ramp,
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct ButtonState {
     pub just_pressed: bool,
@@ -30,6 +50,10 @@ impl InputState {
             buttons: HashMap::new(),
             axes_1d: HashMap::new(),
             axes_2d: HashMap::new(),
+            thresholds: Vec::new(),
+            ramps: Vec::new(),
+            update_count: 0,
+            delta: Duration::ZERO,
 
             mouse_motion: [0.0, 0.0],
             mouse_wheel: [0.0, 0.0],
@@ -38,6 +62,159 @@ impl InputState {
         }
     }
 
+    /// Start tracking the given threshold binding so that it can report when it
+    /// was just pressed or released.
+    pub fn watch_threshold(&mut self, threshold: ThresholdBinding) {
+        if !self.thresholds.iter().any(|(t, _)| *t == threshold) {
+            let state = ButtonState {
+                pressed: self.threshold_value(&threshold) >= threshold.press,
+                ..Default::default()
+            };
+            self.thresholds.push((threshold, state));
+        }
+    }
+
+    /// Start tracking any thresholds or ramped axes used by the given binding.
+    pub fn watch_binding(&mut self, binding: &Binding) {
+        match binding {
+            Binding::Button(_) | Binding::Chord(_) => {}
+            Binding::Threshold(threshold) => self.watch_threshold(*threshold),
+            Binding::Axis1d(axis) => self.watch_axis1d(axis),
+            Binding::Axis2d(Axis2dBinding::Individual { x, y }) => {
+                self.watch_axis1d(x);
+                self.watch_axis1d(y);
+            }
+            Binding::Axis2d(Axis2dBinding::Axis { .. }) => {}
+            Binding::Axis3d(Axis3dBinding::Individual { x, y, z }) => {
+                self.watch_axis1d(x);
+                self.watch_axis1d(y);
+                self.watch_axis1d(z);
+            }
+        }
+    }
+
+    /// Replace the set of tracked thresholds and ramped axes with the ones
+    /// found in `bindings`. Anything that was already tracked keeps its state.
+    pub fn watch_bindings<'a, I>(&mut self, bindings: I)
+    where
+        I: IntoIterator<Item = &'a Binding>,
+    {
+        let previous_thresholds = std::mem::take(&mut self.thresholds);
+        let previous_ramps = std::mem::take(&mut self.ramps);
+
+        for binding in bindings {
+            self.watch_binding(binding);
+        }
+
+        for (threshold, state) in &mut self.thresholds {
+            if let Some((_, previous)) = previous_thresholds.iter().find(|(t, _)| t == threshold) {
+                *state = *previous;
+            }
+        }
+
+        for (ramped, value) in &mut self.ramps {
+            if let Some((_, previous)) = previous_ramps.iter().find(|(r, _)| r == ramped) {
+                *value = *previous;
+            }
+        }
+    }
+
+    fn watch_axis1d(&mut self, axis: &Axis1dBinding) {
+        if let Axis1dBinding::Buttons {
+            neg,
+            pos,
+            ramp: Some(ramp),
+            ..
+        } = *axis
+        {
+            let ramped = RampedAxis { neg, pos, ramp };
+
+            if !self.ramps.iter().any(|(r, _)| *r == ramped) {
+                self.ramps.push((ramped, 0.0));
+            }
+        }
+    }
+
+    /// Returns the current state for the given threshold binding.
+    ///
+    /// Thresholds that aren't being tracked with [`InputState::watch_threshold`]
+    /// are never reported as just pressed or released.
+    pub fn threshold_state(&self, threshold: &ThresholdBinding) -> ButtonState {
+        match self.thresholds.iter().find(|(t, _)| t == threshold) {
+            Some((_, state)) => *state,
+            None => ButtonState {
+                pressed: self.threshold_value(threshold) >= threshold.press,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn threshold_value(&self, threshold: &ThresholdBinding) -> f32 {
+        match threshold.source {
+            ThresholdSource::Axis1d(axis, direction) => {
+                let value = self.get_axis1d(axis);
+
+                match direction {
+                    AxisDirection::Positive => value,
+                    AxisDirection::Negative => -value,
+                }
+            }
+            ThresholdSource::Axis2d(axis, direction) => {
+                let [x, y] = self.get_axis2d(axis);
+
+                match direction {
+                    Axis2dDirection::PositiveX => x,
+                    Axis2dDirection::NegativeX => -x,
+                    Axis2dDirection::PositiveY => y,
+                    Axis2dDirection::NegativeY => -y,
+                }
+            }
+        }
+    }
+
+    /// Returns the current value of an axis composed from two buttons, with
+    /// `neg` pushing it toward -1.0 and `pos` pushing it toward 1.0.
+    pub fn button_axis<B: Into<Button>>(&self, neg: B, pos: B) -> f32 {
+        let neg = -(self.is_button_down(neg) as u8 as f32);
+        let pos = self.is_button_down(pos) as u8 as f32;
+
+        neg + pos
+    }
+
+    /// Returns the current value of a ramped axis composed from two buttons.
+    ///
+    /// Axes that aren't being tracked with [`InputState::watch_binding`] don't
+    /// ramp and behave like [`InputState::button_axis`].
+    pub fn ramped_axis<B: Into<Button>>(&self, neg: B, pos: B, ramp: &Ramp) -> f32 {
+        let ramped = RampedAxis {
+            neg: neg.into(),
+            pos: pos.into(),
+            ramp: *ramp,
+        };
+
+        match self.ramps.iter().find(|(r, _)| *r == ramped) {
+            Some((_, value)) => *value,
+            None => self.button_axis(ramped.neg, ramped.pos),
+        }
+    }
+
+    fn update_thresholds(&mut self) {
+        for i in 0..self.thresholds.len() {
+            let (threshold, mut state) = self.thresholds[i];
+            let value = self.threshold_value(&threshold);
+
+            if !state.pressed && value >= threshold.press {
+                state.just_pressed = true;
+                state.pressed = true;
+            } else if state.pressed && value < threshold.release {
+                state.just_released = true;
+                state.pressed = false;
+            }
+
+            self.thresholds[i].1 = state;
+        }
+    }
+
     /// Sets the offset of the game viewport, used for reporting the cursor's
     /// position.
     pub fn set_viewport_position<P: Into<[f32; 2]>>(&mut self, pos: P) {
@@ -92,6 +269,45 @@ impl InputState {
             .unwrap_or(true)
     }
 
+    /// Tells whether the given input is held down or, for axes, pushed further
+    /// than [`ACTIVE_AXIS_THRESHOLD`] from center.
+    pub fn is_input_active(&self, input: Input) -> bool {
+        let axis1d = |axis: Axis1d| self.get_axis1d(axis).abs() > ACTIVE_AXIS_THRESHOLD;
+        let axis2d = |axis: Axis2d| {
+            let [x, y] = self.get_axis2d(axis);
+            (x * x + y * y).sqrt() > ACTIVE_AXIS_THRESHOLD
+        };
+
+        match input {
+            Input::Keyboard(key) => self.is_button_down(key),
+            Input::Mouse(Mouse::Button(button)) => self.is_button_down(button),
+            Input::Mouse(Mouse::Axis1d(axis)) => axis1d(axis.into()),
+            Input::Mouse(Mouse::Axis2d(axis)) => axis2d(axis.into()),
+            Input::Gamepad(Gamepad::Button(button)) => self.is_button_down(button),
+            Input::Gamepad(Gamepad::Axis1d(axis)) => axis1d(axis.into()),
+            Input::Gamepad(Gamepad::Axis2d(axis)) => axis2d(axis.into()),
+        }
+    }
+
+    /// Lists every button that's held down and every 1D axis that's active, as
+    /// described by [`InputState::is_input_active`]. Gamepad sticks show up as
+    /// their X and Y axes.
+    pub fn active_inputs(&self) -> Vec<Input> {
+        let buttons = self
+            .buttons
+            .iter()
+            .filter(|(_, state)| state.pressed)
+            .map(|(&button, _)| Input::from(button));
+
+        let axes = self
+            .axes_1d
+            .keys()
+            .map(|&axis| Input::from(axis))
+            .filter(|&axis| self.is_input_active(axis));
+
+        buttons.chain(axes).collect()
+    }
+
     /// Tells the state of the given axis.
     pub fn get_axis1d(&self, axis: Axis1d) -> f32 {
         match axis {
@@ -109,7 +325,10 @@ impl InputState {
     pub fn get_axis2d(&self, axis: Axis2d) -> [f32; 2] {
         match axis {
             Axis2d::Mouse(_) => self.mouse_motion,
-            Axis2d::Gamepad(_) => todo!(),
+            Axis2d::Gamepad(axis) => {
+                let (x, y) = axis.components();
+                [self.get_axis1d(x.into()), self.get_axis1d(y.into())]
+            }
         }
     }
 
@@ -124,6 +343,52 @@ impl InputState {
         [x - vx, y - vy]
     }
 
+    /// Tells how many updates have ended since this state was created.
+    pub fn update_count(&self) -> u64 {
+        self.update_count
+    }
+
+    /// Tells how much time passed before the current update, as given to
+    /// [`InputState::begin_update`].
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Marks the start of an update, moving any ramped axes toward their target
+    /// by the time that has passed since the previous update.
+    pub fn begin_update(&mut self, delta: Duration) {
+        self.delta = delta;
+        let delta = delta.as_secs_f32();
+
+        for i in 0..self.ramps.len() {
+            let (ramped, mut value) = self.ramps[i];
+            let target = self.button_axis(ramped.neg, ramped.pos);
+
+            if ramped.ramp.snap && target != 0.0 && value * target < 0.0 {
+                value = 0.0;
+            }
+
+            let rate = if target == 0.0 {
+                ramped.ramp.fall
+            } else {
+                ramped.ramp.rise
+            };
+
+            let step = rate * delta;
+            let distance = target - value;
+
+            // An infinite rate with no time passing produces a NaN step, which
+            // should still land on the target.
+            if step.is_nan() || step >= distance.abs() {
+                value = target;
+            } else {
+                value += step * distance.signum();
+            }
+
+            self.ramps[i].1 = value;
+        }
+    }
+
     /// Marks the end of an update, resetting accumulated mouse motion and
     /// processing buttons being pressed or released.
     pub fn end_update(&mut self) {
@@ -144,6 +409,14 @@ impl InputState {
         for button in to_remove {
             self.buttons.remove(&button);
         }
+
+        for (_, state) in &mut self.thresholds {
+            state.just_pressed = false;
+            state.just_released = false;
+        }
+
+        self.update_thresholds();
+        self.update_count += 1;
     }
 
     /// Handle the given event and update input state accordingly.
@@ -163,6 +436,11 @@ impl InputState {
                 let slot = self.axes_1d.entry(axis).or_default();
                 *slot = value;
             }
+            Event::Axis2dChanged(Axis2d::Gamepad(axis), [x, y]) => {
+                let (x_axis, y_axis) = axis.components();
+                self.axes_1d.insert(x_axis.into(), x);
+                self.axes_1d.insert(y_axis.into(), y);
+            }
             Event::Axis2dChanged(axis, value) => {
                 let slot = self.axes_2d.entry(axis).or_default();
                 *slot = value;
@@ -177,15 +455,27 @@ impl InputState {
                 self.mouse_wheel = [self.mouse_wheel[0] + x, self.mouse_wheel[1] + y];
             }
         }
+
+        self.update_thresholds();
+    }
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::KeyboardKey;
+    use crate::{GamepadAxis1d, GamepadAxis2d, KeyboardKey};
 
     use super::*;
 
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+    }
+
     #[test]
     fn button_state_transition() {
         let mut state = InputState::new();
@@ -271,4 +561,147 @@ mod test {
         state.end_update();
         assert_eq!(state.mouse_motion(), [0.0, 0.0]);
     }
+
+    #[test]
+    fn threshold_hysteresis() {
+        let mut state = InputState::new();
+        let axis = Axis1d::Gamepad(GamepadAxis1d::RightTrigger);
+        let threshold = ThresholdBinding {
+            source: ThresholdSource::Axis1d(axis, AxisDirection::Positive),
+            press: 0.6,
+            release: 0.4,
+        };
+        state.watch_threshold(threshold);
+
+        state.handle_event(Event::Axis1dChanged(axis, 0.5));
+        assert_eq!(state.threshold_state(&threshold), ButtonState::default());
+
+        state.handle_event(Event::Axis1dChanged(axis, 0.7));
+        assert_eq!(
+            state.threshold_state(&threshold),
+            ButtonState {
+                pressed: true,
+                just_pressed: true,
+                just_released: false,
+            }
+        );
+
+        state.end_update();
+        state.handle_event(Event::Axis1dChanged(axis, 0.5));
+        assert_eq!(
+            state.threshold_state(&threshold),
+            ButtonState {
+                pressed: true,
+                just_pressed: false,
+                just_released: false,
+            }
+        );
+
+        state.handle_event(Event::Axis1dChanged(axis, 0.3));
+        assert_eq!(
+            state.threshold_state(&threshold),
+            ButtonState {
+                pressed: false,
+                just_pressed: false,
+                just_released: true,
+            }
+        );
+
+        state.end_update();
+        assert_eq!(state.threshold_state(&threshold), ButtonState::default());
+    }
+
+    #[test]
+    fn threshold_at_release_point() {
+        let mut state = InputState::new();
+        let axis = Axis1d::Gamepad(GamepadAxis1d::RightTrigger);
+        let threshold = ThresholdBinding {
+            source: ThresholdSource::Axis1d(axis, AxisDirection::Positive),
+            press: 0.5,
+            release: 0.5,
+        };
+        state.watch_threshold(threshold);
+
+        state.handle_event(Event::Axis1dChanged(axis, 0.5));
+        assert!(state.threshold_state(&threshold).just_pressed);
+
+        for _ in 0..3 {
+            state.end_update();
+            assert_eq!(
+                state.threshold_state(&threshold),
+                ButtonState {
+                    pressed: true,
+                    just_pressed: false,
+                    just_released: false,
+                }
+            );
+        }
+
+        state.handle_event(Event::Axis1dChanged(axis, 0.49));
+        assert!(state.threshold_state(&threshold).just_released);
+    }
+
+    #[test]
+    fn threshold_stick_direction() {
+        let mut state = InputState::new();
+        let threshold = ThresholdBinding {
+            source: ThresholdSource::Axis2d(
+                Axis2d::Gamepad(GamepadAxis2d::LeftStick),
+                Axis2dDirection::NegativeX,
+            ),
+            press: 0.5,
+            release: 0.5,
+        };
+        state.watch_threshold(threshold);
+
+        state.handle_event(Event::Axis1dChanged(GamepadAxis1d::LeftStickX.into(), 0.9));
+        assert!(!state.threshold_state(&threshold).pressed);
+
+        state.handle_event(Event::Axis2dChanged(
+            GamepadAxis2d::LeftStick.into(),
+            [-0.9, 0.0],
+        ));
+        assert!(state.threshold_state(&threshold).just_pressed);
+        assert_eq!(state.get_axis1d(GamepadAxis1d::LeftStickX.into()), -0.9);
+    }
+
+    #[test]
+    fn ramped_axis() {
+        let mut state = InputState::new();
+        let ramp = Ramp {
+            rise: 2.0,
+            fall: 4.0,
+            snap: true,
+        };
+        state.watch_binding(&Binding::Axis1d(
+            Axis1dBinding::buttons(KeyboardKey::A, KeyboardKey::D).with_ramp(ramp),
+        ));
+
+        let tick = |state: &mut InputState| {
+            state.begin_update(Duration::from_millis(100));
+            state.end_update();
+        };
+        let value = |state: &InputState| state.ramped_axis(KeyboardKey::A, KeyboardKey::D, &ramp);
+
+        state.handle_event(Event::ButtonPressed(KeyboardKey::D.into()));
+        tick(&mut state);
+        assert_close(value(&state), 0.2);
+        tick(&mut state);
+        assert_close(value(&state), 0.4);
+
+        state.handle_event(Event::ButtonReleased(KeyboardKey::D.into()));
+        tick(&mut state);
+        assert_close(value(&state), 0.0);
+
+        state.handle_event(Event::ButtonPressed(KeyboardKey::D.into()));
+        for _ in 0..10 {
+            tick(&mut state);
+        }
+        assert_close(value(&state), 1.0);
+
+        state.handle_event(Event::ButtonReleased(KeyboardKey::D.into()));
+        state.handle_event(Event::ButtonPressed(KeyboardKey::A.into()));
+        tick(&mut state);
+        assert_close(value(&state), -0.2);
+    }
 }